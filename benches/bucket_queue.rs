@@ -25,7 +25,7 @@ fn benchmark(bencher: &mut Bencher, items: usize, buckets: usize) {
             subject.enqueue(*value, *priority);
         }
 
-        while let Some(_) = subject.dequeue_min() { }
+        while subject.dequeue_min().is_some() { }
     });
 }
 
@@ -66,7 +66,7 @@ fn benchmark_nested(bencher: &mut Bencher, items: usize, buckets: usize) {
             subject.bucket(*outer_priority).enqueue(*value, *inner_priority);
         }
 
-        while let Some(_) = subject.min_bucket().dequeue_min() { }
+        while subject.min_bucket().dequeue_min().is_some() { }
     });
 }
 
@@ -69,7 +69,7 @@ trait BiggestFirstQueue<B: BiggestFirstBucket>: Queue<B> {
     }
 }
 
-impl<B: BiggestFirstBucket> BiggestFirstQueue<B> for BucketQueue<B> { }
+impl<B: BiggestFirstBucket, I: Index> BiggestFirstQueue<B> for BucketQueue<B, I> { }
 
 
 // -----------------------
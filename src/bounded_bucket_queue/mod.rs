@@ -0,0 +1,172 @@
+use super::*;
+
+// The insertion methods of BoundedBucketQueue shadow those of the queue traits
+// and apply the Overflow policy when the queue is full. Items are evicted with
+// the bucket's usual removal method, e.g. FIFO buckets evict their oldest item.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    Reject,
+    EvictMin,
+    EvictMax,
+}
+
+pub struct BoundedBucketQueue<B: Bucket, I: Index = SimpleIndex, P: Priority = usize> {
+    queue: BucketQueue<B, I, P>,
+    capacity: usize,
+    overflow: Overflow,
+}
+
+type Evict<B, I, P> = fn(&mut BucketQueue<B, I, P>, P) -> Option<<B as Bucket>::Item>;
+
+impl<B: Bucket, I: Index, P: Priority> BoundedBucketQueue<B, I, P> {
+    pub fn new(capacity: usize, overflow: Overflow) -> Self {
+        Self { queue: BucketQueue::new(), capacity, overflow }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() >= self.capacity
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    // Returns Ok with the evicted item (if any) when there is room for another
+    // item, or Err if the item should be rejected.
+    fn make_room(&mut self, evict: Evict<B, I, P>) -> Result<Option<B::Item>, ()> {
+        if !self.is_full() {
+            return Ok(None);
+        }
+
+        let priority = match self.overflow {
+            Overflow::Reject => None,
+            Overflow::EvictMin => self.queue.min_priority(),
+            Overflow::EvictMax => self.queue.max_priority(),
+        };
+
+        match priority.and_then(|p| evict(&mut self.queue, p)) {
+            Some(item) => Ok(Some(item)),
+            None => Err(()),
+        }
+    }
+
+    fn panic_if_full(&self, n: usize) {
+        if self.len() + n > self.capacity {
+            panic!("BoundedBucketQueue is full. Use its insertion methods to apply the Overflow policy.");
+        }
+    }
+}
+
+impl<B: FirstInFirstOutBucket, I: Index, P: Priority> BoundedBucketQueue<B, I, P> {
+    pub fn enqueue(&mut self, item: B::Item, priority: P) -> Result<Option<B::Item>, B::Item> {
+        match self.make_room(<BucketQueue<B, I, P> as FirstInFirstOutQueue<B, P>>::dequeue) {
+            Ok(evicted) => { self.queue.enqueue(item, priority); Ok(evicted) },
+            Err(()) => Err(item),
+        }
+    }
+}
+
+impl<B: LastInFirstOutBucket, I: Index, P: Priority> BoundedBucketQueue<B, I, P> {
+    pub fn push(&mut self, item: B::Item, priority: P) -> Result<Option<B::Item>, B::Item> {
+        match self.make_room(<BucketQueue<B, I, P> as LastInFirstOutQueue<B, P>>::pop) {
+            Ok(evicted) => { self.queue.push(item, priority); Ok(evicted) },
+            Err(()) => Err(item),
+        }
+    }
+}
+
+impl<B: DoubleEndedBucket, I: Index, P: Priority> BoundedBucketQueue<B, I, P> {
+    pub fn push_back(&mut self, item: B::Item, priority: P) -> Result<Option<B::Item>, B::Item> {
+        match self.make_room(<BucketQueue<B, I, P> as DoubleEndedQueue<B, P>>::pop_front) {
+            Ok(evicted) => { self.queue.push_back(item, priority); Ok(evicted) },
+            Err(()) => Err(item),
+        }
+    }
+
+    pub fn push_front(&mut self, item: B::Item, priority: P) -> Result<Option<B::Item>, B::Item> {
+        match self.make_room(<BucketQueue<B, I, P> as DoubleEndedQueue<B, P>>::pop_back) {
+            Ok(evicted) => { self.queue.push_front(item, priority); Ok(evicted) },
+            Err(()) => Err(item),
+        }
+    }
+}
+
+impl<B: Bucket, I: Index, P: Priority> Queue<B, P> for BoundedBucketQueue<B, I, P> {
+    fn new_queue() -> Self {
+        panic!("BoundedBucketQueue should be initialized with BoundedBucketQueue::new(capacity, overflow).");
+    }
+
+    fn min_priority(&self) -> Option<P> {
+        self.queue.min_priority()
+    }
+
+    fn max_priority(&self) -> Option<P> {
+        self.queue.max_priority()
+    }
+
+    fn min_priority_from(&self, from: P) -> Option<P> {
+        self.queue.min_priority_from(from)
+    }
+
+    fn max_priority_to(&self, to: P) -> Option<P> {
+        self.queue.max_priority_to(to)
+    }
+
+    fn bucket_for_adding(&mut self, priority: P) -> &mut B {
+        self.panic_if_full(1);
+        self.queue.bucket_for_adding(priority)
+    }
+
+    fn bucket_for_removing(&mut self, priority: P) -> Option<&mut B> {
+        self.queue.bucket_for_removing(priority)
+    }
+
+    fn bucket_for_peeking(&self, priority: P) -> Option<&B> {
+        self.queue.bucket_for_peeking(priority)
+    }
+
+    fn bucket_for_peeking_mut(&mut self, priority: P) -> Option<&mut B> {
+        self.queue.bucket_for_peeking_mut(priority)
+    }
+
+    fn bucket_for_replacing(&mut self, priority: P) -> &mut Option<B> {
+        self.queue.bucket_for_replacing(priority)
+    }
+
+    fn items_replaced(&mut self, priority: P, old_size: usize, new_size: usize) {
+        self.queue.items_replaced(priority, old_size, new_size)
+    }
+
+    fn len_queue(&self) -> usize {
+        self.len()
+    }
+
+    fn is_empty_queue(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn replace(&mut self, priority: P, replacement: Option<B>) -> Option<B> {
+        let old_size = self.queue.bucket_for_peeking(priority).map_or(0, |b| b.len_bucket());
+        let new_size = replacement.as_ref().map_or(0, |b| b.len_bucket());
+
+        self.panic_if_full(new_size.saturating_sub(old_size));
+        self.queue.replace(priority, replacement)
+    }
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+// Moves all items from other into self, leaving other empty. The items from
+// other are treated as though they were added after the items in self.
+
+pub trait AppendableBucket: Bucket {
+    fn append_bucket(&mut self, other: &mut Self);
+}
+
+// ---------------------------------------------------------------------------
+// Provide canonical implementations of AppendableBucket for VecDeque and Vec:
+// ---------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+
+// Other's items are added to the back so they're dequeued after self's items.
+impl<T> AppendableBucket for VecDeque<T> {
+    fn append_bucket(&mut self, other: &mut Self) {
+        self.append(other)
+    }
+}
+
+// Other's items are added to the top so they're popped before self's items.
+impl<T> AppendableBucket for Vec<T> {
+    fn append_bucket(&mut self, other: &mut Self) {
+        self.append(other)
+    }
+}
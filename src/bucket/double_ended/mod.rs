@@ -6,6 +6,12 @@ pub trait DoubleEndedBucket: Bucket {
 
     fn pop_back(&mut self) -> Option<Self::Item>;
     fn pop_front(&mut self) -> Option<Self::Item>;
+
+    fn peek_back(&self) -> Option<&Self::Item>;
+    fn peek_front(&self) -> Option<&Self::Item>;
+
+    fn peek_back_mut(&mut self) -> Option<&mut Self::Item>;
+    fn peek_front_mut(&mut self) -> Option<&mut Self::Item>;
 }
 
 // -----------------------------------------------------------------------
@@ -30,6 +36,22 @@ impl<T> DoubleEndedBucket for VecDeque<T> {
     fn pop_front(&mut self) -> Option<Self::Item> {
         self.pop_front()
     }
+
+    fn peek_back(&self) -> Option<&Self::Item> {
+        self.back()
+    }
+
+    fn peek_front(&self) -> Option<&Self::Item> {
+        self.front()
+    }
+
+    fn peek_back_mut(&mut self) -> Option<&mut Self::Item> {
+        self.back_mut()
+    }
+
+    fn peek_front_mut(&mut self) -> Option<&mut Self::Item> {
+        self.front_mut()
+    }
 }
 
 
@@ -37,9 +59,10 @@ impl<T> DoubleEndedBucket for VecDeque<T> {
 // Implement DoubleEndedBucket for DeferredBucket to support deferral:
 // -------------------------------------------------------------------
 
-impl<'a, Q, B> DoubleEndedBucket for DeferredBucket<'a, Q, B>
-    where Q: DoubleEndedQueue<B>,
+impl<'a, Q, B, P> DoubleEndedBucket for DeferredBucket<'a, Q, B, P>
+    where Q: DoubleEndedQueue<B, P>,
           B: DoubleEndedBucket,
+          P: Priority,
 {
     fn push_back(&mut self, item: Self::Item) {
         self.adding().push_back(item)
@@ -50,10 +73,26 @@ impl<'a, Q, B> DoubleEndedBucket for DeferredBucket<'a, Q, B>
     }
 
     fn pop_back(&mut self) -> Option<Self::Item> {
-        self.removing()?.pop_back()
+        self.removing_with(B::pop_back)
     }
 
     fn pop_front(&mut self) -> Option<Self::Item> {
-        self.removing()?.pop_front()
+        self.removing_with(B::pop_front)
+    }
+
+    fn peek_back(&self) -> Option<&Self::Item> {
+        self.peeking()?.peek_back()
+    }
+
+    fn peek_front(&self) -> Option<&Self::Item> {
+        self.peeking()?.peek_front()
+    }
+
+    fn peek_back_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peeking_mut()?.peek_back_mut()
+    }
+
+    fn peek_front_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peeking_mut()?.peek_front_mut()
     }
 }
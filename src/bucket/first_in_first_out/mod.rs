@@ -4,6 +4,9 @@ pub trait FirstInFirstOutBucket: Bucket {
     fn enqueue(&mut self, item: Self::Item);
 
     fn dequeue(&mut self) -> Option<Self::Item>;
+
+    fn peek(&self) -> Option<&Self::Item>;
+    fn peek_mut(&mut self) -> Option<&mut Self::Item>;
 }
 
 // ---------------------------------------------------------------------------
@@ -20,6 +23,14 @@ impl<T> FirstInFirstOutBucket for VecDeque<T> {
     fn dequeue(&mut self) -> Option<Self::Item> {
         self.pop_front()
     }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.front()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut Self::Item> {
+        self.front_mut()
+    }
 }
 
 
@@ -27,15 +38,24 @@ impl<T> FirstInFirstOutBucket for VecDeque<T> {
 // Implement FirstInFirstOutBucket for DeferredBucket to support deferral:
 // -----------------------------------------------------------------------
 
-impl<'a, Q, B> FirstInFirstOutBucket for DeferredBucket<'a, Q, B>
-    where Q: FirstInFirstOutQueue<B>,
+impl<'a, Q, B, P> FirstInFirstOutBucket for DeferredBucket<'a, Q, B, P>
+    where Q: FirstInFirstOutQueue<B, P>,
           B: FirstInFirstOutBucket,
+          P: Priority,
 {
     fn enqueue(&mut self, item: Self::Item) {
         self.adding().enqueue(item)
     }
 
     fn dequeue(&mut self) -> Option<Self::Item> {
-        self.removing()?.dequeue()
+        self.removing_with(B::dequeue)
+    }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.peeking()?.peek()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peeking_mut()?.peek_mut()
     }
 }
@@ -0,0 +1,43 @@
+use super::*;
+
+pub trait IterableBucket: Bucket {
+    type Iter<'a>: DoubleEndedIterator<Item=&'a Self::Item> where Self: 'a;
+    type IterMut<'a>: DoubleEndedIterator<Item=&'a mut Self::Item> where Self: 'a;
+
+    fn iter_bucket(&self) -> Self::Iter<'_>;
+
+    fn iter_bucket_mut(&mut self) -> Self::IterMut<'_>;
+}
+
+// -------------------------------------------------------------------------
+// Provide canonical implementations of IterableBucket for VecDeque and Vec:
+// -------------------------------------------------------------------------
+
+use std::collections::{vec_deque, VecDeque};
+use std::slice;
+
+impl<T> IterableBucket for VecDeque<T> {
+    type Iter<'a> = vec_deque::Iter<'a, T> where T: 'a;
+    type IterMut<'a> = vec_deque::IterMut<'a, T> where T: 'a;
+
+    fn iter_bucket(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+
+    fn iter_bucket_mut(&mut self) -> Self::IterMut<'_> {
+        self.iter_mut()
+    }
+}
+
+impl<T> IterableBucket for Vec<T> {
+    type Iter<'a> = slice::Iter<'a, T> where T: 'a;
+    type IterMut<'a> = slice::IterMut<'a, T> where T: 'a;
+
+    fn iter_bucket(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+
+    fn iter_bucket_mut(&mut self) -> Self::IterMut<'_> {
+        self.iter_mut()
+    }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+// Buckets of keys for KeyedBucketQueue. The bucket's queueing semantic decides
+// which key is removed first when several keys share the same priority.
+
+pub trait KeyedBucket: Bucket {
+    fn push_key(&mut self, key: Self::Item);
+
+    fn pop_key(&mut self) -> Option<Self::Item>;
+
+    fn peek_key(&self) -> Option<&Self::Item>;
+
+    fn remove_key(&mut self, key: &Self::Item) -> bool;
+}
+
+// -------------------------------------------------------------------------
+// Provide First-In-First-Out tie-breaking for KeyedBucketQueue via VecDeque:
+// -------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+
+impl<K: PartialEq> KeyedBucket for VecDeque<K> {
+    fn push_key(&mut self, key: Self::Item) {
+        self.enqueue(key)
+    }
+
+    fn pop_key(&mut self) -> Option<Self::Item> {
+        self.dequeue()
+    }
+
+    fn peek_key(&self) -> Option<&Self::Item> {
+        FirstInFirstOutBucket::peek(self)
+    }
+
+    fn remove_key(&mut self, key: &Self::Item) -> bool {
+        let position = self.iter().position(|k| k == key);
+        position.and_then(|p| self.remove(p)).is_some()
+    }
+}
+
+// --------------------------------------------------------------------
+// Provide Last-In-First-Out tie-breaking for KeyedBucketQueue via Vec:
+// --------------------------------------------------------------------
+
+impl<K: PartialEq> KeyedBucket for Vec<K> {
+    fn push_key(&mut self, key: Self::Item) {
+        self.push(key)
+    }
+
+    fn pop_key(&mut self) -> Option<Self::Item> {
+        self.pop()
+    }
+
+    fn peek_key(&self) -> Option<&Self::Item> {
+        LastInFirstOutBucket::peek(self)
+    }
+
+    fn remove_key(&mut self, key: &Self::Item) -> bool {
+        match self.iter().position(|k| k == key) {
+            Some(position) => { self.remove(position); true },
+            None => false,
+        }
+    }
+}
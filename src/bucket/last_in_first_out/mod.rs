@@ -4,6 +4,9 @@ pub trait LastInFirstOutBucket: Bucket {
     fn push(&mut self, item: Self::Item);
 
     fn pop(&mut self) -> Option<Self::Item>;
+
+    fn peek(&self) -> Option<&Self::Item>;
+    fn peek_mut(&mut self) -> Option<&mut Self::Item>;
 }
 
 // ---------------------------------------------------------------------
@@ -18,6 +21,14 @@ impl<T> LastInFirstOutBucket for Vec<T> {
     fn pop(&mut self) -> Option<Self::Item> {
         self.pop()
     }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.last()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut Self::Item> {
+        self.last_mut()
+    }
 }
 
 
@@ -25,15 +36,24 @@ impl<T> LastInFirstOutBucket for Vec<T> {
 // Implement LastInFirstOutBucket for DeferredBucket to support deferral:
 // ----------------------------------------------------------------------
 
-impl<'a, Q, B> LastInFirstOutBucket for DeferredBucket<'a, Q, B>
-    where Q: LastInFirstOutQueue<B>,
+impl<'a, Q, B, P> LastInFirstOutBucket for DeferredBucket<'a, Q, B, P>
+    where Q: LastInFirstOutQueue<B, P>,
           B: LastInFirstOutBucket,
+          P: Priority,
 {
     fn push(&mut self, item: Self::Item) {
         self.adding().push(item)
     }
 
     fn pop(&mut self) -> Option<Self::Item> {
-        self.removing()?.pop()
+        self.removing_with(B::pop)
+    }
+
+    fn peek(&self) -> Option<&Self::Item> {
+        self.peeking()?.peek()
+    }
+
+    fn peek_mut(&mut self) -> Option<&mut Self::Item> {
+        self.peeking_mut()?.peek_mut()
     }
 }
@@ -1,6 +1,11 @@
+pub mod appendable;
 pub mod double_ended;
 pub mod first_in_first_out;
+pub mod iterable;
+pub mod keyed;
 pub mod last_in_first_out;
+pub mod reservable;
+pub mod retainable;
 
 use super::*;
 
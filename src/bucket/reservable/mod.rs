@@ -0,0 +1,23 @@
+use super::*;
+
+pub trait ReservableBucket: Bucket {
+    fn reserve_bucket(&mut self, additional: usize);
+}
+
+// ---------------------------------------------------------------------------
+// Provide canonical implementations of ReservableBucket for VecDeque and Vec:
+// ---------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+
+impl<T> ReservableBucket for VecDeque<T> {
+    fn reserve_bucket(&mut self, additional: usize) {
+        self.reserve(additional)
+    }
+}
+
+impl<T> ReservableBucket for Vec<T> {
+    fn reserve_bucket(&mut self, additional: usize) {
+        self.reserve(additional)
+    }
+}
@@ -0,0 +1,81 @@
+use super::*;
+
+pub trait RetainableBucket: Bucket {
+    fn retain_bucket<F>(&mut self, f: F) where F: FnMut(&Self::Item) -> bool;
+
+    fn remove_first_from_bucket<F>(&mut self, f: F) -> Option<Self::Item>
+        where F: FnMut(&Self::Item) -> bool;
+}
+
+// ---------------------------------------------------------------------------
+// Provide canonical implementations of RetainableBucket for VecDeque and Vec:
+// ---------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+
+impl<T> RetainableBucket for VecDeque<T> {
+    fn retain_bucket<F>(&mut self, f: F) where F: FnMut(&T) -> bool {
+        self.retain(f)
+    }
+
+    fn remove_first_from_bucket<F>(&mut self, f: F) -> Option<T>
+        where F: FnMut(&T) -> bool
+    {
+        let position = self.iter().position(f)?;
+        self.remove(position)
+    }
+}
+
+impl<T> RetainableBucket for Vec<T> {
+    fn retain_bucket<F>(&mut self, f: F) where F: FnMut(&T) -> bool {
+        self.retain(f)
+    }
+
+    fn remove_first_from_bucket<F>(&mut self, f: F) -> Option<T>
+        where F: FnMut(&T) -> bool
+    {
+        let position = self.iter().position(f)?;
+        Some(self.remove(position))
+    }
+}
+
+// ------------------------------------------------------------------
+// Implement RetainableBucket for DeferredBucket to support deferral:
+// ------------------------------------------------------------------
+
+impl<'a, Q, B, P> RetainableBucket for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: RetainableBucket,
+          P: Priority,
+{
+    fn retain_bucket<F>(&mut self, f: F) where F: FnMut(&Self::Item) -> bool {
+        if self.is_empty() {
+            return;
+        }
+
+        let bucket = self.replacing();
+        let old_size = bucket.len_bucket();
+
+        bucket.retain_bucket(f);
+        let new_size = bucket.len_bucket();
+
+        self.replaced(old_size, new_size);
+    }
+
+    fn remove_first_from_bucket<F>(&mut self, f: F) -> Option<Self::Item>
+        where F: FnMut(&Self::Item) -> bool
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let bucket = self.replacing();
+        let old_size = bucket.len_bucket();
+
+        let removed = bucket.remove_first_from_bucket(f);
+        let new_size = bucket.len_bucket();
+
+        self.replaced(old_size, new_size);
+        removed
+    }
+}
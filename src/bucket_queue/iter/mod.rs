@@ -0,0 +1,232 @@
+use super::*;
+
+use std::iter::{Enumerate, FilterMap, Map};
+use std::slice;
+
+impl<B: Bucket, I: Index, P: Priority> BucketQueue<B, I, P> {
+    pub fn iter_buckets(&self) -> IterBuckets<'_, B, P> {
+        let occupied: Occupied<'_, B> = |(position, bucket)| {
+            Some((position, bucket.as_ref().filter(|b| !b.is_empty_bucket())?))
+        };
+
+        let buckets = self.buckets.iter().enumerate().filter_map(occupied);
+        IterBuckets { inner: Offsets::new(buckets, self.base) }
+    }
+}
+
+impl<B: IterableBucket, I: Index, P: Priority> BucketQueue<B, I, P> {
+    pub fn iter(&self) -> Iter<'_, B, P> {
+        let items: Items<'_, B, P> = |(priority, bucket)| (priority, bucket.iter_bucket());
+
+        Iter { inner: Flatten::new(self.iter_buckets().map(items)) }
+    }
+
+    // Items can be modified in place, but buckets can't be resized which
+    // keeps the index consistent.
+    pub fn iter_mut(&mut self) -> IterMut<'_, B, P> {
+        let occupied: OccupiedMut<'_, B> = |(position, bucket)| {
+            let bucket = bucket.as_mut().filter(|b| !b.is_empty_bucket())?;
+            Some((position, bucket.iter_bucket_mut()))
+        };
+
+        let buckets = self.buckets.iter_mut().enumerate().filter_map(occupied);
+        IterMut { inner: Flatten::new(Offsets::new(buckets, self.base)) }
+    }
+}
+
+// --------------------------------------------------------------------------
+// Iterators over the non-empty buckets and items of BucketQueue by priority:
+// --------------------------------------------------------------------------
+
+type Buckets<'a, B> = Enumerate<slice::Iter<'a, Option<B>>>;
+type BucketsMut<'a, B> = Enumerate<slice::IterMut<'a, Option<B>>>;
+
+type Occupied<'a, B> = fn((usize, &'a Option<B>)) -> Option<(usize, &'a B)>;
+
+pub struct IterBuckets<'a, B: Bucket, P: Priority> {
+    inner: Offsets<FilterMap<Buckets<'a, B>, Occupied<'a, B>>, P>,
+}
+
+impl<'a, B: Bucket, P: Priority> Iterator for IterBuckets<'a, B, P> {
+    type Item = (P, &'a B);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a, B: Bucket, P: Priority> DoubleEndedIterator for IterBuckets<'a, B, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+type Items<'a, B, P> = fn((P, &'a B)) -> (P, <B as IterableBucket>::Iter<'a>);
+type ItemsOfBuckets<'a, B, P> = Map<IterBuckets<'a, B, P>, Items<'a, B, P>>;
+
+pub struct Iter<'a, B: IterableBucket, P: Priority> {
+    inner: Flatten<ItemsOfBuckets<'a, B, P>, B::Iter<'a>, P>,
+}
+
+impl<'a, B: IterableBucket, P: Priority> Iterator for Iter<'a, B, P> {
+    type Item = (P, &'a B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a, B: IterableBucket, P: Priority> DoubleEndedIterator for Iter<'a, B, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+type OccupiedMut<'a, B> =
+    fn((usize, &'a mut Option<B>)) -> Option<(usize, <B as IterableBucket>::IterMut<'a>)>;
+type ItemsOfBucketsMut<'a, B, P> = Offsets<FilterMap<BucketsMut<'a, B>, OccupiedMut<'a, B>>, P>;
+
+pub struct IterMut<'a, B: IterableBucket, P: Priority> {
+    inner: Flatten<ItemsOfBucketsMut<'a, B, P>, B::IterMut<'a>, P>,
+}
+
+impl<'a, B: IterableBucket, P: Priority> Iterator for IterMut<'a, B, P> {
+    type Item = (P, &'a mut B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+impl<'a, B: IterableBucket, P: Priority> DoubleEndedIterator for IterMut<'a, B, P> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+// Converts the positions of buckets into priorities. Positions are enumerated
+// rather than zipped with a range of offsets because the range would overflow
+// when the highest offset is usize::MAX.
+struct Offsets<O, P> {
+    positions: O,
+    base: usize,
+    phantom: PhantomData<P>,
+}
+
+impl<O, P> Offsets<O, P> {
+    fn new(positions: O, base: usize) -> Self {
+        Self { positions, base, phantom: PhantomData }
+    }
+}
+
+impl<O, T, P> Iterator for Offsets<O, P>
+    where O: Iterator<Item=(usize, T)>,
+          P: Priority,
+{
+    type Item = (P, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (position, value) = self.positions.next()?;
+        Some((P::from_offset(position + self.base), value))
+    }
+}
+
+impl<O, T, P> DoubleEndedIterator for Offsets<O, P>
+    where O: DoubleEndedIterator<Item=(usize, T)>,
+          P: Priority,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (position, value) = self.positions.next_back()?;
+        Some((P::from_offset(position + self.base), value))
+    }
+}
+
+// Flattens (priority, bucket iterator) pairs into (priority, item) pairs. The
+// front and back are tracked separately so that it can be reversed.
+struct Flatten<O, T, P> {
+    buckets: O,
+    front: Option<(P, T)>,
+    back: Option<(P, T)>,
+}
+
+impl<O, T, P> Flatten<O, T, P> {
+    fn new(buckets: O) -> Self {
+        Self { buckets, front: None, back: None }
+    }
+}
+
+impl<O, T, P> Iterator for Flatten<O, T, P>
+    where O: DoubleEndedIterator<Item=(P, T)>,
+          T: DoubleEndedIterator,
+          P: Copy,
+{
+    type Item = (P, T::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((priority, items)) = &mut self.front {
+                if let Some(item) = items.next() {
+                    return Some((*priority, item));
+                }
+            }
+
+            match self.buckets.next() {
+                Some(bucket) => self.front = Some(bucket),
+                None => {
+                    let (priority, items) = self.back.as_mut()?;
+                    return items.next().map(|item| (*priority, item));
+                },
+            }
+        }
+    }
+}
+
+impl<O, T, P> DoubleEndedIterator for Flatten<O, T, P>
+    where O: DoubleEndedIterator<Item=(P, T)>,
+          T: DoubleEndedIterator,
+          P: Copy,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((priority, items)) = &mut self.back {
+                if let Some(item) = items.next_back() {
+                    return Some((*priority, item));
+                }
+            }
+
+            match self.buckets.next_back() {
+                Some(bucket) => self.back = Some(bucket),
+                None => {
+                    let (priority, items) = self.front.as_mut()?;
+                    return items.next_back().map(|item| (*priority, item));
+                },
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------
+// Implement IterableBucket for BucketQueue to support nested iteration:
+// ---------------------------------------------------------------------
+
+type WithoutPriority<'a, T, P> = fn((P, &'a T)) -> &'a T;
+type WithoutPriorityMut<'a, T, P> = fn((P, &'a mut T)) -> &'a mut T;
+
+impl<B, I, P> IterableBucket for BucketQueue<B, I, P>
+    where B: IterableBucket,
+          I: Index,
+          P: Priority,
+{
+    type Iter<'a> = Map<Iter<'a, B, P>, WithoutPriority<'a, B::Item, P>>
+        where Self: 'a;
+    type IterMut<'a> = Map<IterMut<'a, B, P>, WithoutPriorityMut<'a, B::Item, P>>
+        where Self: 'a;
+
+    fn iter_bucket(&self) -> Self::Iter<'_> {
+        self.iter().map(|(_, item)| item)
+    }
+
+    fn iter_bucket_mut(&mut self) -> Self::IterMut<'_> {
+        self.iter_mut().map(|(_, item)| item)
+    }
+}
@@ -1,14 +1,26 @@
+pub mod iter;
+
 use super::*;
-use std::mem::replace;
+use std::iter::{once, repeat_with, FromIterator};
+use std::marker::PhantomData;
+use std::mem::{replace, swap, take};
+
+// Buckets that are dropped when the range of priorities moves upwards (or when
+// the queue is cleared) can be kept in a pool and reused rather than dropped.
+// The pool is disabled by default. See #set_max_pooled.
 
-pub struct BucketQueue<B: Bucket, I: Index = SimpleIndex> {
+pub struct BucketQueue<B: Bucket, I: Index = SimpleIndex, P: Priority = usize> {
     buckets: Vec<Option<B>>,
+    base: usize,
     index: I,
+    pool: Vec<B>,
+    max_pooled: usize,
+    phantom: PhantomData<P>,
 }
 
-impl<B: Bucket, I: Index> BucketQueue<B, I> {
+impl<B: Bucket, I: Index, P: Priority> BucketQueue<B, I, P> {
     pub fn new() -> Self {
-        Self { buckets: Vec::new(), index: I::new() }
+        Self::from_buckets(Vec::new(), 0)
     }
 
     pub fn len(&self) -> usize {
@@ -19,56 +31,412 @@ impl<B: Bucket, I: Index> BucketQueue<B, I> {
         self.index.is_empty()
     }
 
-    fn grow(&mut self, priority: usize) -> &mut Option<B> {
-        for _ in self.buckets.len()..=priority {
+    // The number of bucket slots currently allocated, including empty ones.
+    pub fn len_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn len_pool(&self) -> usize {
+        self.pool.len()
+    }
+
+    pub fn set_max_pooled(&mut self, max_pooled: usize) {
+        self.max_pooled = max_pooled;
+        self.pool.truncate(max_pooled);
+    }
+
+    // Makes sure a bucket is allocated for the priority so that adding items
+    // to it doesn't need to allocate.
+    pub fn reserve_priority(&mut self, priority: P) {
+        self.reserve_range(priority.to_offset(), priority.to_offset());
+        self.allocate(priority.to_offset() - self.base);
+    }
+
+    // Drops empty buckets and the pool, then trims the bucket slots so that
+    // they only span from the min priority to the max priority.
+    pub fn shrink_to_fit(&mut self) {
+        for bucket in &mut self.buckets {
+            if bucket.as_ref().is_some_and(|b| b.is_empty_bucket()) {
+                *bucket = None;
+            }
+        }
+
+        match (self.index.min(), self.index.max()) {
+            (Some(min), Some(max)) => {
+                self.buckets.truncate(max + 1);
+                self.rebase(min + self.base);
+            },
+            _ => {
+                self.buckets = Vec::new();
+                self.base = 0;
+            },
+        }
+
+        self.buckets.shrink_to_fit();
+        self.pool = Vec::new();
+    }
+
+    // Returns a queue containing the buckets >= priority, leaving the buckets
+    // below it. The buckets are moved rather than their items.
+    pub fn split_off(&mut self, priority: P) -> Self {
+        let offset = priority.to_offset();
+
+        if offset <= self.base {
+            return self.take_buckets();
+        }
+
+        let position = offset - self.base;
+
+        if position >= self.buckets.len() {
+            return Self::new();
+        }
+
+        let buckets = self.buckets.split_off(position);
+        self.reindex();
+
+        Self::from_buckets(buckets, offset)
+    }
+
+    // Returns a queue containing the buckets < priority, leaving the buckets
+    // above and including it.
+    pub fn split_below(&mut self, priority: P) -> Self {
+        let offset = priority.to_offset();
+
+        if offset <= self.base {
+            return Self::new();
+        }
+
+        let position = offset - self.base;
+
+        if position >= self.buckets.len() {
+            return self.take_buckets();
+        }
+
+        let upper = self.buckets.split_off(position);
+        let lower = replace(&mut self.buckets, upper);
+        let base = replace(&mut self.base, offset);
+        self.unbase();
+        self.reindex();
+
+        Self::from_buckets(lower, base)
+    }
+
+    fn from_buckets(buckets: Vec<Option<B>>, base: usize) -> Self {
+        let (index, pool, phantom) = (I::new(), Vec::new(), PhantomData);
+        let mut queue = Self { buckets, base, index, pool, max_pooled: 0, phantom };
+
+        queue.unbase();
+        queue.reindex();
+        queue
+    }
+
+    // The pools stay where they are so that each queue keeps its own settings.
+    fn swap_buckets(&mut self, other: &mut Self) {
+        swap(&mut self.buckets, &mut other.buckets);
+        swap(&mut self.base, &mut other.base);
+        swap(&mut self.index, &mut other.index);
+    }
+
+    fn take_buckets(&mut self) -> Self {
+        let mut queue = Self::new();
+        self.swap_buckets(&mut queue);
+        queue
+    }
+
+    // Rebuilds the index from scratch in time proportional to the number of
+    // buckets.
+    fn reindex(&mut self) {
+        self.index = I::new();
+
+        for (position, bucket) in self.buckets.iter().enumerate() {
+            let n = bucket.as_ref().map_or(0, |b| b.len_bucket());
+
+            if n > 0 {
+                self.index.added_n(n, position, &self.buckets);
+            }
+        }
+    }
+
+    // Buckets are stored relative to a base offset so that memory is
+    // proportional to the range of priorities, rather than the largest one.
+    fn grow(&mut self, offset: usize) -> &mut Option<B> {
+        self.grow_range(offset, offset);
+        &mut self.buckets[offset - self.base]
+    }
+
+    // If the queue is empty, its buckets are recycled rather than growing the
+    // range downwards, which would keep slots for priorities that aren't used.
+    fn grow_range(&mut self, min: usize, max: usize) {
+        if self.index.is_empty() && min < self.base {
+            self.clear();
+        }
+
+        self.reserve_range(min, max);
+    }
+
+    // Makes sure there are slots for every offset from min to max.
+    fn reserve_range(&mut self, min: usize, max: usize) {
+        if self.buckets.is_empty() {
+            self.base = min;
+            self.unbase();
+        } else if min < self.base {
+            self.rebase(min);
+        } else if max - self.base >= self.buckets.len() {
+            self.reclaim(min);
+        }
+
+        for _ in self.buckets.len()..=(max - self.base) {
             self.buckets.push(None);
         }
+    }
+
+    // Drops the buckets below the minimum priority if they make up at least
+    // half of the buckets. This keeps the amortized cost of growing constant.
+    fn reclaim(&mut self, offset: usize) {
+        let lowest = match self.index.min() {
+            Some(min) => (min + self.base).min(offset),
+            None => offset,
+        };
+
+        if lowest - self.base >= self.buckets.len() / 2 {
+            self.rebase(lowest);
+        }
+    }
 
-        &mut self.buckets[priority]
+    fn rebase(&mut self, new_base: usize) {
+        if !I::supports_rebase() {
+            return;
+        }
+
+        if new_base < self.base {
+            let extra = repeat_with(|| None).take(self.base - new_base);
+            self.buckets.splice(0..0, extra);
+        } else {
+            let n = self.buckets.len().min(new_base - self.base);
+
+            for bucket in self.buckets.drain(..n).flatten() {
+                if self.pool.len() < self.max_pooled {
+                    self.pool.push(bucket);
+                }
+            }
+        }
+
+        self.index.rebase(self.base, new_base);
+        self.base = new_base;
+    }
+
+    // Indexes that don't support rebasing are used with a base of zero, i.e.
+    // there are slots for every priority from zero to the max.
+    fn unbase(&mut self) {
+        if !I::supports_rebase() && self.base > 0 {
+            self.buckets.splice(0..0, repeat_with(|| None).take(self.base));
+            self.base = 0;
+        }
+    }
+
+    fn position(&self, priority: P) -> Option<usize> {
+        priority.to_offset().checked_sub(self.base)
+    }
+
+    // Returns the bucket at the position, reusing a pooled bucket if needed.
+    fn allocate(&mut self, position: usize) -> &mut B {
+        let pool = &mut self.pool;
+        self.buckets[position].get_or_insert_with(|| pool.pop().unwrap_or_else(B::new_bucket))
+    }
+}
+
+impl<B: ReservableBucket, I: Index, P: Priority> BucketQueue<B, I, P> {
+    // Allocates buckets from priority zero (or max_priority if it's negative) to
+    // max_priority, each with enough capacity for per_bucket items.
+    pub fn with_capacity(max_priority: P, per_bucket: usize) -> Self {
+        let mut queue = Self::new();
+        let min_priority = max_priority.min(P::default());
+
+        queue.grow_range(min_priority.to_offset(), max_priority.to_offset());
+
+        for position in 0..queue.buckets.len() {
+            queue.allocate(position).reserve_bucket(per_bucket);
+        }
+
+        queue
+    }
+}
+
+impl<B: AppendableBucket, I: Index, P: Priority> BucketQueue<B, I, P> {
+    // Moves each of other's buckets into the matching bucket of self and
+    // updates the index once per bucket, leaving other empty.
+    pub fn append(&mut self, other: &mut Self) {
+        if self.is_empty() {
+            self.swap_buckets(other);
+            return;
+        }
+
+        let (min, max) = match (other.index.min(), other.index.max()) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return,
+        };
+
+        self.grow_range(min + other.base, max + other.base);
+
+        for position in min..=max {
+            if let Some(bucket) = &mut other.buckets[position] {
+                let n = bucket.len_bucket();
+                let target = position + other.base - self.base;
+
+                if n > 0 {
+                    self.allocate(target).append_bucket(bucket);
+                    self.index.added_n(n, target, &self.buckets);
+                }
+            }
+        }
+
+        other.clear();
+    }
+}
+
+impl<B: RetainableBucket, I: Index, P: Priority> BucketQueue<B, I, P> {
+    pub fn retain<F>(&mut self, mut f: F) where F: FnMut(P, &B::Item) -> bool {
+        for position in 0..self.buckets.len() {
+            let priority = P::from_offset(position + self.base);
+
+            if let Some(bucket) = &mut self.buckets[position] {
+                let old_size = bucket.len_bucket();
+
+                bucket.retain_bucket(|item| f(priority, item));
+                let new_size = bucket.len_bucket();
+
+                self.items_replaced(priority, old_size, new_size);
+            }
+        }
+    }
+
+    // Items are checked in ascending priority, then in the order they're stored
+    // in each bucket (see IterableBucket).
+    pub fn remove_first_where<F>(&mut self, mut f: F) -> Option<(P, B::Item)>
+        where F: FnMut(P, &B::Item) -> bool
+    {
+        for position in 0..self.buckets.len() {
+            let priority = P::from_offset(position + self.base);
+
+            if let Some(bucket) = &mut self.buckets[position] {
+                if let Some(item) = bucket.remove_first_from_bucket(|item| f(priority, item)) {
+                    let new_size = bucket.len_bucket();
+                    self.items_replaced(priority, new_size + 1, new_size);
+
+                    return Some((priority, item));
+                }
+            }
+        }
+
+        None
     }
 }
 
-impl<B: Bucket> Queue<B> for BucketQueue<B> {
+impl<B: Bucket, I: Index, P: Priority> Default for BucketQueue<B, I, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Items are added to buckets in the order they're iterated, so this works for
+// First-In-First-Out, Last-In-First-Out and Double-Ended buckets. The buckets
+// are sized up front and the index is updated once per bucket.
+
+impl<T, B, I, P> Extend<(T, P)> for BucketQueue<B, I, P>
+    where B: Bucket<Item=T> + Extend<T>,
+          I: Index,
+          P: Priority,
+{
+    fn extend<J: IntoIterator<Item=(T, P)>>(&mut self, iter: J) {
+        let items: Vec<_> = iter.into_iter().map(|(item, p)| (item, p.to_offset())).collect();
+
+        let min = items.iter().map(|(_, offset)| *offset).min();
+        let max = items.iter().map(|(_, offset)| *offset).max();
+
+        let (min, max) = match (min, max) {
+            (Some(min), Some(max)) => (min, max),
+            _ => return,
+        };
+
+        self.grow_range(min, max);
+
+        let mut counts = vec![0; max - min + 1];
+
+        for (item, offset) in items {
+            self.allocate(offset - self.base).extend(once(item));
+            counts[offset - min] += 1;
+        }
+
+        for (i, n) in counts.into_iter().enumerate().filter(|(_, n)| *n > 0) {
+            self.index.added_n(n, min + i - self.base, &self.buckets);
+        }
+    }
+}
+
+impl<T, B, I, P> FromIterator<(T, P)> for BucketQueue<B, I, P>
+    where B: Bucket<Item=T> + Extend<T>,
+          I: Index,
+          P: Priority,
+{
+    fn from_iter<J: IntoIterator<Item=(T, P)>>(iter: J) -> Self {
+        let mut queue = Self::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<B: Bucket, I: Index, P: Priority> Queue<B, P> for BucketQueue<B, I, P> {
     fn new_queue() -> Self {
         Self::new()
     }
 
-    fn min_priority(&self) -> Option<usize> {
-        self.index.min()
+    fn min_priority(&self) -> Option<P> {
+        Some(P::from_offset(self.index.min()? + self.base))
     }
 
-    fn max_priority(&self) -> Option<usize> {
-        self.index.max()
+    fn max_priority(&self) -> Option<P> {
+        Some(P::from_offset(self.index.max()? + self.base))
     }
 
-    fn bucket_for_adding(&mut self, priority: usize) -> &mut B {
-        self.index.add(priority, &self.buckets);
+    fn bucket_for_adding(&mut self, priority: P) -> &mut B {
+        self.grow(priority.to_offset());
 
-        self.grow(priority).get_or_insert_with(|| B::new_bucket())
+        let position = priority.to_offset() - self.base;
+        self.index.add(position, &self.buckets);
+
+        self.allocate(position)
     }
 
-    fn bucket_for_removing(&mut self, priority: usize) -> Option<&mut B> {
-        self.index.remove(priority, &self.buckets);
+    fn bucket_for_removing(&mut self, priority: P) -> Option<&mut B> {
+        let position = self.position(priority)?;
+        self.index.remove(position, &self.buckets);
+
+        self.buckets.get_mut(position)?.as_mut()
+    }
 
-        self.buckets.get_mut(priority)?.as_mut()
+    fn bucket_for_peeking(&self, priority: P) -> Option<&B> {
+        self.buckets.get(self.position(priority)?)?.as_ref()
     }
 
-    fn bucket_for_peeking(&self, priority: usize) -> Option<&B> {
-        self.buckets.get(priority)?.as_ref()
+    fn bucket_for_peeking_mut(&mut self, priority: P) -> Option<&mut B> {
+        let position = self.position(priority)?;
+        self.buckets.get_mut(position)?.as_mut()
     }
 
-    fn bucket_for_replacing(&mut self, priority: usize) -> &mut Option<B> {
+    fn bucket_for_replacing(&mut self, priority: P) -> &mut Option<B> {
         // The index is not automatically updated as there is no way to tell how
         // many items will be replaced. Instead, #items_replaced must be called.
 
-        self.grow(priority)
+        self.grow(priority.to_offset())
     }
 
-    fn items_replaced(&mut self, priority: usize, old_size: usize, new_size: usize) {
+    fn items_replaced(&mut self, priority: P, old_size: usize, new_size: usize) {
+        let position = priority.to_offset() - self.base;
+
         if new_size > old_size {
-            self.index.added_n(new_size - old_size, priority, &self.buckets);
+            self.index.added_n(new_size - old_size, position, &self.buckets);
         } else if new_size < old_size {
-            self.index.removed_n(old_size - new_size, priority, &self.buckets);
+            self.index.removed_n(old_size - new_size, position, &self.buckets);
         }
     }
 
@@ -80,8 +448,8 @@ impl<B: Bucket> Queue<B> for BucketQueue<B> {
         self.is_empty()
     }
 
-    fn replace(&mut self, priority: usize, replacement: Option<B>) -> Option<B> {
-        let existing = self.grow(priority);
+    fn replace(&mut self, priority: P, replacement: Option<B>) -> Option<B> {
+        let existing = self.grow(priority.to_offset());
 
         let old_size = existing.as_ref().map_or(0, |b| b.len_bucket());
         let new_size = replacement.as_ref().map_or(0, |b| b.len_bucket());
@@ -93,7 +461,7 @@ impl<B: Bucket> Queue<B> for BucketQueue<B> {
     }
 }
 
-impl<T, B: Bucket<Item=T>, I: Index> Bucket for BucketQueue<B, I> {
+impl<T, B: Bucket<Item=T>, I: Index, P: Priority> Bucket for BucketQueue<B, I, P> {
     type Item = T;
 
     fn new_bucket() -> Self {
@@ -109,7 +477,32 @@ impl<T, B: Bucket<Item=T>, I: Index> Bucket for BucketQueue<B, I> {
     }
 
     fn clear(&mut self) {
-        self.buckets = Vec::new();
+        for mut bucket in take(&mut self.buckets).into_iter().flatten() {
+            if self.pool.len() < self.max_pooled {
+                bucket.clear();
+                self.pool.push(bucket);
+            }
+        }
+
+        self.base = 0;
         self.index = I::new();
     }
 }
+
+impl<B: RetainableBucket, I: Index, P: Priority> RetainableBucket for BucketQueue<B, I, P> {
+    fn retain_bucket<F>(&mut self, mut f: F) where F: FnMut(&B::Item) -> bool {
+        self.retain(|_, item| f(item))
+    }
+
+    fn remove_first_from_bucket<F>(&mut self, mut f: F) -> Option<B::Item>
+        where F: FnMut(&B::Item) -> bool
+    {
+        self.remove_first_where(|_, item| f(item)).map(|(_, item)| item)
+    }
+}
+
+impl<B: AppendableBucket, I: Index, P: Priority> AppendableBucket for BucketQueue<B, I, P> {
+    fn append_bucket(&mut self, other: &mut Self) {
+        self.append(other)
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+const BITS: usize = 64;
+
+// Each bucket has its own lock so that threads only contend when they use the
+// same priority. A bitmap records which buckets are non-empty so that the min
+// and max can be found without locking. A bucket's bit is only changed while
+// its lock is held, so the bit is accurate whenever the lock is free.
+//
+// Ordering guarantees are relaxed compared to a single lock:
+// - Items with the same priority are First-In-First-Out
+// - dequeue_min returns an item from the lowest priority bucket that was seen
+//   to be non-empty while scanning the bitmap. An item enqueued with a lower
+//   priority during the scan may be missed and returned by a later call.
+// - None is only returned if every bucket was seen to be empty during the scan
+// - len is a snapshot and may be out of date as soon as it's returned
+
+pub struct ConcurrentBucketQueue<T> {
+    buckets: Vec<Mutex<VecDeque<T>>>,
+    words: Vec<AtomicU64>,
+    len: AtomicUsize,
+}
+
+impl<T> ConcurrentBucketQueue<T> {
+    pub fn new(max_priority: usize) -> Self {
+        let buckets = (0..=max_priority).map(|_| Mutex::new(VecDeque::new())).collect();
+        let words = (0..=max_priority / BITS).map(|_| AtomicU64::new(0)).collect();
+
+        Self { buckets, words, len: AtomicUsize::new(0) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn max_priority_allowed(&self) -> usize {
+        self.buckets.len() - 1
+    }
+
+    pub fn min_priority(&self) -> Option<usize> {
+        self.find_min(0)
+    }
+
+    pub fn max_priority(&self) -> Option<usize> {
+        self.find_max(self.max_priority_allowed())
+    }
+
+    pub fn enqueue(&self, item: T, priority: usize) {
+        let mut bucket = self.lock(priority);
+
+        bucket.push_back(item);
+        self.len.fetch_add(1, Ordering::AcqRel);
+
+        if bucket.len() == 1 {
+            self.set(priority);
+        }
+    }
+
+    pub fn dequeue(&self, priority: usize) -> Option<T> {
+        if priority > self.max_priority_allowed() {
+            return None;
+        }
+
+        let mut bucket = self.lock(priority);
+        let item = bucket.pop_front()?;
+
+        self.len.fetch_sub(1, Ordering::AcqRel);
+
+        if bucket.is_empty() {
+            self.unset(priority);
+        }
+
+        Some(item)
+    }
+
+    pub fn dequeue_min(&self) -> Option<T> {
+        self.dequeue_min_with_priority().map(|(_, item)| item)
+    }
+
+    pub fn dequeue_max(&self) -> Option<T> {
+        self.dequeue_max_with_priority().map(|(_, item)| item)
+    }
+
+    // If another thread empties the bucket first, the scan continues from the
+    // next priority rather than starting again.
+    pub fn dequeue_min_with_priority(&self) -> Option<(usize, T)> {
+        let mut priority = self.find_min(0)?;
+
+        loop {
+            if let Some(item) = self.dequeue(priority) {
+                return Some((priority, item));
+            }
+
+            priority = self.find_min(priority + 1)?;
+        }
+    }
+
+    pub fn dequeue_max_with_priority(&self) -> Option<(usize, T)> {
+        let mut priority = self.find_max(self.max_priority_allowed())?;
+
+        loop {
+            if let Some(item) = self.dequeue(priority) {
+                return Some((priority, item));
+            }
+
+            priority = self.find_max(priority.checked_sub(1)?)?;
+        }
+    }
+
+    // Poisoning is ignored because the lock is never held while calling code
+    // that could leave a bucket in an inconsistent state.
+    fn lock(&self, priority: usize) -> MutexGuard<'_, VecDeque<T>> {
+        match self.buckets.get(priority) {
+            Some(mutex) => mutex.lock().unwrap_or_else(|e| e.into_inner()),
+            None => panic!("Priority is greater than the max priority of the ConcurrentBucketQueue."),
+        }
+    }
+
+    fn set(&self, priority: usize) {
+        self.words[priority / BITS].fetch_or(1 << (priority % BITS), Ordering::AcqRel);
+    }
+
+    fn unset(&self, priority: usize) {
+        self.words[priority / BITS].fetch_and(!(1 << (priority % BITS)), Ordering::AcqRel);
+    }
+
+    // Finds the lowest non-empty priority that is at least `from`.
+    fn find_min(&self, from: usize) -> Option<usize> {
+        let mut index = from / BITS;
+        let mut mask = u64::MAX << (from % BITS);
+
+        while let Some(word) = self.words.get(index) {
+            let word = word.load(Ordering::Acquire) & mask;
+
+            if word != 0 {
+                return Some(index * BITS + word.trailing_zeros() as usize);
+            }
+
+            index += 1;
+            mask = u64::MAX;
+        }
+
+        None
+    }
+
+    // Finds the highest non-empty priority that is at most `to`.
+    fn find_max(&self, to: usize) -> Option<usize> {
+        let to = to.min(self.max_priority_allowed());
+
+        let mut index = to / BITS;
+        let mut mask = u64::MAX >> (BITS - 1 - to % BITS);
+
+        loop {
+            let word = self.words[index].load(Ordering::Acquire) & mask;
+
+            if word != 0 {
+                return Some(index * BITS + (BITS - 1 - word.leading_zeros() as usize));
+            }
+
+            index = index.checked_sub(1)?;
+            mask = u64::MAX;
+        }
+    }
+}
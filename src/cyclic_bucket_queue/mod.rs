@@ -0,0 +1,198 @@
+use super::*;
+use std::marker::PhantomData;
+use std::mem::replace;
+
+// The min and max are stored as offsets (see Priority) so that the span of the
+// window can be calculated for any type of priority.
+
+pub struct CyclicBucketQueue<B: Bucket, P: Priority = usize> {
+    buckets: Vec<Option<B>>,
+    len: usize,
+    min: Option<usize>,
+    max: Option<usize>,
+    phantom: PhantomData<P>,
+}
+
+impl<B: Bucket, P: Priority> CyclicBucketQueue<B, P> {
+    pub fn new(max_span: usize) -> Self {
+        let buckets = (0..=max_span).map(|_| None).collect();
+
+        Self { buckets, len: 0, min: None, max: None, phantom: PhantomData }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The largest difference allowed between the min and max priority.
+    pub fn max_span(&self) -> usize {
+        self.buckets.len() - 1
+    }
+
+    pub fn in_window(&self, priority: P) -> bool {
+        let priority = priority.to_offset();
+
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => {
+                max.max(priority) - min.min(priority) <= self.max_span()
+            },
+            _ => true,
+        }
+    }
+
+    fn in_range(&self, priority: usize) -> bool {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => min <= priority && priority <= max,
+            _ => false,
+        }
+    }
+
+    fn slot(&mut self, priority: usize) -> &mut Option<B> {
+        let n = self.buckets.len();
+        &mut self.buckets[priority % n]
+    }
+
+    fn size_of_slot(&self, priority: usize) -> usize {
+        let n = self.buckets.len();
+        self.buckets[priority % n].as_ref().map_or(0, |b| b.len_bucket())
+    }
+
+    fn panic_unless_in_window(&self, priority: P) {
+        if !self.in_window(priority) {
+            panic!("Priority is outside the window of the CyclicBucketQueue.");
+        }
+    }
+
+    fn include(&mut self, priority: usize) {
+        self.min = Some(self.min.map_or(priority, |min| min.min(priority)));
+        self.max = Some(self.max.map_or(priority, |max| max.max(priority)));
+    }
+
+    fn exclude(&mut self, priority: usize) {
+        if let (Some(min), Some(max)) = (self.min, self.max) {
+            if priority == min {
+                self.min = self.find_next_priority((min + 1)..=max);
+            }
+
+            if priority == max {
+                self.max = self.find_next_priority((min..max).rev());
+            }
+        }
+    }
+
+    fn find_next_priority<R: Iterator<Item=usize>>(&self, mut range: R) -> Option<usize> {
+        range.find(|&p| self.size_of_slot(p) > 0)
+    }
+}
+
+impl<B: Bucket, P: Priority> Queue<B, P> for CyclicBucketQueue<B, P> {
+    fn new_queue() -> Self {
+        panic!("CyclicBucketQueue should be initialized with CyclicBucketQueue::new(max_span).");
+    }
+
+    fn min_priority(&self) -> Option<P> {
+        self.min.map(P::from_offset)
+    }
+
+    fn max_priority(&self) -> Option<P> {
+        self.max.map(P::from_offset)
+    }
+
+    fn bucket_for_adding(&mut self, priority: P) -> &mut B {
+        self.panic_unless_in_window(priority);
+        let priority = priority.to_offset();
+
+        self.len += 1;
+        self.include(priority);
+
+        self.slot(priority).get_or_insert_with(|| B::new_bucket())
+    }
+
+    fn bucket_for_removing(&mut self, priority: P) -> Option<&mut B> {
+        let priority = priority.to_offset();
+
+        if !self.in_range(priority) {
+            return None;
+        }
+
+        // This is called before the item is removed from the bucket.
+        let size = self.size_of_slot(priority);
+
+        if size > 0 {
+            self.len -= 1;
+        }
+
+        if size == 1 {
+            self.exclude(priority);
+        }
+
+        self.slot(priority).as_mut()
+    }
+
+    fn bucket_for_peeking(&self, priority: P) -> Option<&B> {
+        let priority = priority.to_offset();
+
+        if !self.in_range(priority) {
+            return None;
+        }
+
+        self.buckets[priority % self.buckets.len()].as_ref()
+    }
+
+    fn bucket_for_peeking_mut(&mut self, priority: P) -> Option<&mut B> {
+        let priority = priority.to_offset();
+
+        if !self.in_range(priority) {
+            return None;
+        }
+
+        self.slot(priority).as_mut()
+    }
+
+    fn bucket_for_replacing(&mut self, priority: P) -> &mut Option<B> {
+        // The window is checked here because the slot might belong to a
+        // different priority. Afterwards, #items_replaced must be called.
+
+        self.panic_unless_in_window(priority);
+        self.slot(priority.to_offset())
+    }
+
+    fn items_replaced(&mut self, priority: P, old_size: usize, new_size: usize) {
+        let priority = priority.to_offset();
+        self.len = (self.len + new_size).saturating_sub(old_size);
+
+        if new_size > 0 {
+            self.include(priority);
+        } else if old_size > 0 {
+            self.exclude(priority);
+        }
+    }
+
+    fn len_queue(&self) -> usize {
+        self.len()
+    }
+
+    fn is_empty_queue(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn replace(&mut self, priority: P, replacement: Option<B>) -> Option<B> {
+        if replacement.is_none() && !self.in_range(priority.to_offset()) {
+            return None;
+        }
+
+        let existing = self.bucket_for_replacing(priority);
+
+        let old_size = existing.as_ref().map_or(0, |b| b.len_bucket());
+        let new_size = replacement.as_ref().map_or(0, |b| b.len_bucket());
+
+        let replaced = replace(existing, replacement);
+        self.items_replaced(priority, old_size, new_size);
+
+        replaced
+    }
+}
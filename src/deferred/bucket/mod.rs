@@ -2,46 +2,107 @@ use super::*;
 
 use std::marker::PhantomData;
 
-pub struct DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
+pub struct DeferredBucket<'a, Q, B, P = usize>
+    where Q: Queue<B, P>,
           B: Bucket,
+          P: Priority,
 {
     queue: &'a mut Q,
-    priority: usize,
+    priority: P,
     consumed: bool,
     phantom: PhantomData<B>,
 }
 
-impl<'a, Q, B> DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
+impl<'a, Q, B, P> DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
           B: Bucket,
+          P: Priority,
 {
-    pub fn new(queue: &'a mut Q, priority: usize) -> Self {
+    pub fn new(queue: &'a mut Q, priority: P) -> Self {
         Self { queue, priority, consumed: false, phantom: PhantomData }
     }
 
+    pub fn priority(&self) -> P {
+        self.priority
+    }
+
+    // Prepends this bucket's priority to the result of f so that nested queues
+    // can return the full path of priorities to an item, e.g. (1, (2, item)).
+    pub fn with_priority<T, F>(mut self, f: F) -> Option<(P, T)>
+        where F: FnOnce(&mut Self) -> Option<T>
+    {
+        let priority = self.priority;
+        f(&mut self).map(|result| (priority, result))
+    }
+
     pub fn len(&self) -> usize {
         self.peeking().map_or(0, |b| b.len_bucket())
     }
 
     pub fn is_empty(&self) -> bool {
-        self.peeking().map_or(true, |b| b.is_empty_bucket())
+        self.peeking().is_none_or(|b| b.is_empty_bucket())
+    }
+
+    // Whether an item has been added to or removed from the bucket already.
+    pub fn is_consumed(&self) -> bool {
+        self.consumed
     }
 
     pub fn adding(&mut self) -> &mut B {
-        self.panic_if_consumed();
-        self.queue.bucket_for_adding(self.priority)
+        match self.try_adding() {
+            Ok(bucket) => bucket,
+            Err(error) => panic!("{}", error),
+        }
     }
 
     pub fn removing(&mut self) -> Option<&mut B> {
-        self.panic_if_consumed();
-        self.queue.bucket_for_removing(self.priority)
+        match self.try_removing() {
+            Ok(bucket) => bucket,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    // Like #removing, but the queue is updated after f removes the item, which
+    // lets it see the bucket's new size, e.g. to drop the bucket if it's empty.
+    pub fn removing_with<T, F>(&mut self, f: F) -> Option<T>
+        where F: FnOnce(&mut B) -> Option<T>
+    {
+        if let Err(error) = self.consume() {
+            panic!("{}", error);
+        }
+
+        if self.is_empty() {
+            return None;
+        }
+
+        let bucket = self.replacing();
+        let old_size = bucket.len_bucket();
+
+        let result = f(bucket);
+        let new_size = bucket.len_bucket();
+
+        self.replaced(old_size, new_size);
+        result
+    }
+
+    pub fn try_adding(&mut self) -> Result<&mut B, Error> {
+        self.consume()?;
+        Ok(self.queue.bucket_for_adding(self.priority))
+    }
+
+    pub fn try_removing(&mut self) -> Result<Option<&mut B>, Error> {
+        self.consume()?;
+        Ok(self.queue.bucket_for_removing(self.priority))
     }
 
     pub fn peeking(&self) -> Option<&B> {
         self.queue.bucket_for_peeking(self.priority)
     }
 
+    pub fn peeking_mut(&mut self) -> Option<&mut B> {
+        self.queue.bucket_for_peeking_mut(self.priority)
+    }
+
     pub fn replacing(&mut self) -> &mut B {
         self.queue.bucket_for_replacing(self.priority)
             .get_or_insert_with(|| B::new_bucket())
@@ -52,23 +113,25 @@ impl<'a, Q, B> DeferredBucket<'a, Q, B>
         self.queue.items_replaced(self.priority, old_size, new_size);
     }
 
-    fn panic_if_consumed(&mut self) {
+    fn consume(&mut self) -> Result<(), Error> {
         if self.consumed {
-            panic!("You may only add or remove a single item from the bucket.")
+            return Err(Error::BucketConsumed);
         }
 
-        self.consumed = true
+        self.consumed = true;
+        Ok(())
     }
 }
 
-impl<'a, Q, B> Bucket for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
+impl<'a, Q, B, P> Bucket for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
           B: Bucket,
+          P: Priority,
 {
     type Item = B::Item;
 
     fn new_bucket() -> Self {
-        panic!("DeferredBucket should not be initialized this way.");
+        panic!("{}", Error::DeferredInitialization);
     }
 
     fn len_bucket(&self) -> usize {
@@ -88,45 +151,60 @@ impl<'a, Q, B> Bucket for DeferredBucket<'a, Q, B>
 // Implement Queue for DeferredBucket if the Bucket type is also a Queue:
 // ----------------------------------------------------------------------
 
-impl<'a, Q, B, C> Queue<C> for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
-          B: Bucket + Queue<C>,
+impl<'a, Q, B, C, P, R> Queue<C, R> for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket + Queue<C, R>,
           C: Bucket,
+          P: Priority,
+          R: Priority,
 {
     fn new_queue() -> Self {
-        panic!("DeferredBucket should not be initialized this way.");
+        panic!("{}", Error::DeferredInitialization);
     }
 
-    fn min_priority(&self) -> Option<usize> {
+    fn min_priority(&self) -> Option<R> {
         self.peeking()?.min_priority()
     }
 
-    fn max_priority(&self) -> Option<usize> {
+    fn max_priority(&self) -> Option<R> {
         self.peeking()?.max_priority()
     }
 
-    fn bucket_for_adding(&mut self, priority: usize) -> &mut C {
+    fn min_priority_from(&self, from: R) -> Option<R> {
+        self.peeking()?.min_priority_from(from)
+    }
+
+    fn max_priority_to(&self, to: R) -> Option<R> {
+        self.peeking()?.max_priority_to(to)
+    }
+
+    fn bucket_for_adding(&mut self, priority: R) -> &mut C {
         self.adding().bucket_for_adding(priority)
     }
 
-    fn bucket_for_removing(&mut self, priority: usize) -> Option<&mut C> {
+    fn bucket_for_removing(&mut self, priority: R) -> Option<&mut C> {
         self.removing()?.bucket_for_removing(priority)
     }
 
-    fn bucket_for_peeking(&self, priority: usize) -> Option<&C> {
+    fn bucket_for_peeking(&self, priority: R) -> Option<&C> {
         self.peeking()?.bucket_for_peeking(priority)
     }
 
-    fn bucket_for_replacing(&mut self, priority: usize) -> &mut Option<C> {
+    fn bucket_for_peeking_mut(&mut self, priority: R) -> Option<&mut C> {
+        self.peeking_mut()?.bucket_for_peeking_mut(priority)
+    }
+
+    fn bucket_for_replacing(&mut self, priority: R) -> &mut Option<C> {
         self.replacing().bucket_for_replacing(priority)
     }
 
-    fn items_replaced(&mut self, priority: usize, old_size: usize, new_size: usize) {
+    fn items_replaced(&mut self, priority: R, old_size: usize, new_size: usize) {
+        // Update the current queue's index first so that the parent queue sees
+        // its new size, e.g. so it can tell whether the bucket is now empty.
+        self.replacing().items_replaced(priority, old_size, new_size);
+
         // Update the parent queue's index.
         self.replaced(old_size, new_size);
-
-        // Update the current queue's index.
-        self.replacing().items_replaced(priority, old_size, new_size);
     }
 
     fn len_queue(&self) -> usize {
@@ -134,10 +212,10 @@ impl<'a, Q, B, C> Queue<C> for DeferredBucket<'a, Q, B>
     }
 
     fn is_empty_queue(&self) -> bool {
-        self.peeking().map_or(true, |q| q.is_empty_queue())
+        self.peeking().is_none_or(|q| q.is_empty_queue())
     }
 
-    fn replace(&mut self, priority: usize, replacement: Option<C>) -> Option<C> {
+    fn replace(&mut self, priority: R, replacement: Option<C>) -> Option<C> {
         let queue = self.replacing();
         let existing = queue.bucket_for_replacing(priority);
 
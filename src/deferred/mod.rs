@@ -2,24 +2,59 @@ pub mod bucket;
 
 use super::*;
 
-pub trait Deferred<B: Bucket>: Queue<B> + Sized {
-    fn bucket(&mut self, priority: usize) -> DeferredBucket<Self, B> {
+pub trait Deferred<B: Bucket, P: Priority = usize>: Queue<B, P> + Sized {
+    fn bucket(&mut self, priority: P) -> DeferredBucket<'_, Self, B, P> {
         DeferredBucket::new(self, priority)
     }
 
-    fn min_bucket(&mut self) -> DeferredBucket<Self, B> {
-        self.bucket(self.min_priority().unwrap_or(0))
+    fn try_bucket(&mut self, priority: P) -> Result<DeferredBucket<'_, Self, B, P>, Error> {
+        Ok(self.bucket(priority))
     }
 
-    fn max_bucket(&mut self) -> DeferredBucket<Self, B> {
-        self.bucket(self.max_priority().unwrap_or(0))
+    // Unlike a DeferredBucket, f can add or remove any number of items. The
+    // queue's index is updated afterwards by comparing the size of the bucket.
+    fn with_bucket<T, F>(&mut self, priority: P, f: F) -> T
+        where F: FnOnce(&mut B) -> T
+    {
+        let bucket = self.bucket_for_replacing(priority).get_or_insert_with(B::new_bucket);
+        let old_size = bucket.len_bucket();
+
+        let result = f(bucket);
+        let new_size = bucket.len_bucket();
+
+        self.items_replaced(priority, old_size, new_size);
+        result
+    }
+
+    fn min_bucket(&mut self) -> DeferredBucket<'_, Self, B, P> {
+        self.bucket(self.min_priority().unwrap_or_default())
+    }
+
+    fn max_bucket(&mut self) -> DeferredBucket<'_, Self, B, P> {
+        self.bucket(self.max_priority().unwrap_or_default())
     }
 }
 
-impl<B: Bucket> Deferred<B> for BucketQueue<B> { }
+impl<B: Bucket, I: Index, P: Priority> Deferred<B, P> for BucketQueue<B, I, P> { }
 
-impl<'a, Q, B, C> Deferred<C> for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
-          B: Bucket + Queue<C>,
+impl<B: Bucket, P: Priority> Deferred<B, P> for CyclicBucketQueue<B, P> { }
+
+impl<B: Bucket, P: Priority> Deferred<B, P> for SparseBucketQueue<B, P> { }
+
+impl<'a, Q, B, C, P, R> Deferred<C, R> for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket + Queue<C, R>,
           C: Bucket,
-{ }
+          P: Priority,
+          R: Priority,
+{
+    // A nested bucket can't be used if this bucket has been consumed because
+    // adding or removing items from it would consume this bucket again.
+    fn try_bucket(&mut self, priority: R) -> Result<DeferredBucket<'_, Self, C, R>, Error> {
+        if self.is_consumed() {
+            return Err(Error::BucketConsumed);
+        }
+
+        Ok(self.bucket(priority))
+    }
+}
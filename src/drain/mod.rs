@@ -0,0 +1,131 @@
+use super::*;
+
+use std::marker::PhantomData;
+use std::ops::{Bound, RangeBounds};
+
+// Items are removed one at a time and the queue's index is updated after each
+// one, so it stays consistent even if the Drain is dropped before it's
+// exhausted. Buckets are accessed in the same way as #with_bucket rather than
+// through #bucket_for_removing so that nested DeferredBuckets can be drained.
+
+pub struct Drain<'a, Q, B, P = usize>
+    where Q: Queue<B, P>,
+          B: Bucket,
+          P: Priority,
+{
+    queue: &'a mut Q,
+    pop: fn(&mut B) -> Option<B::Item>,
+    bounds: Option<(usize, usize)>,
+    min_first: bool,
+    phantom: PhantomData<(B, P)>,
+}
+
+impl<'a, Q, B, P> Drain<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket,
+          P: Priority,
+{
+    pub fn min_first<R>(queue: &'a mut Q, range: R, pop: fn(&mut B) -> Option<B::Item>) -> Self
+        where R: RangeBounds<P>
+    {
+        Self { queue, pop, bounds: Self::offsets(range), min_first: true, phantom: PhantomData }
+    }
+
+    pub fn max_first<R>(queue: &'a mut Q, range: R, pop: fn(&mut B) -> Option<B::Item>) -> Self
+        where R: RangeBounds<P>
+    {
+        Self { queue, pop, bounds: Self::offsets(range), min_first: false, phantom: PhantomData }
+    }
+
+    // Converts the range to inclusive offsets or None if the range is empty.
+    fn offsets<R: RangeBounds<P>>(range: R) -> Option<(usize, usize)> {
+        let lower = match range.start_bound() {
+            Bound::Included(p) => p.to_offset(),
+            Bound::Excluded(p) => p.to_offset().checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+
+        let upper = match range.end_bound() {
+            Bound::Included(p) => p.to_offset(),
+            Bound::Excluded(p) => p.to_offset().checked_sub(1)?,
+            Bound::Unbounded => usize::MAX,
+        };
+
+        if lower <= upper { Some((lower, upper)) } else { None }
+    }
+
+    // Jumps to the next non-empty bucket in the range. The bounds are clamped to
+    // the queue's min and max first so that they are valid priorities.
+    fn next_offset(&mut self) -> Option<usize> {
+        let (lower, upper) = self.bounds?;
+
+        let min = self.queue.min_priority()?.to_offset();
+        let max = self.queue.max_priority()?.to_offset();
+
+        if lower > max || upper < min {
+            return None;
+        }
+
+        let offset = if self.min_first {
+            self.queue.min_priority_from(P::from_offset(lower.max(min)))?.to_offset()
+        } else {
+            self.queue.max_priority_to(P::from_offset(upper.min(max)))?.to_offset()
+        };
+
+        if offset < lower || offset > upper {
+            return None;
+        }
+
+        // Nothing is added while draining, so the buckets that were skipped
+        // over don't need to be checked again.
+        self.bounds = if self.min_first { Some((offset, upper)) } else { Some((lower, offset)) };
+
+        Some(offset)
+    }
+
+    fn remove(&mut self, priority: P) -> Option<B::Item> {
+        let pop = self.pop;
+        let bucket = self.queue.bucket_for_replacing(priority);
+
+        let old_size = bucket.as_ref().map_or(0, |b| b.len_bucket());
+        let item = bucket.as_mut().and_then(pop);
+        let new_size = bucket.as_ref().map_or(0, |b| b.len_bucket());
+
+        self.queue.items_replaced(priority, old_size, new_size);
+        item
+    }
+
+    // Narrows the bounds so that they no longer include the offset.
+    fn exclude(&mut self, offset: usize) {
+        self.bounds = match self.bounds {
+            Some((_, upper)) if self.min_first && offset < upper => Some((offset + 1, upper)),
+            Some((lower, _)) if !self.min_first && offset > lower => Some((lower, offset - 1)),
+            _ => None,
+        };
+    }
+}
+
+impl<'a, Q, B, P> Iterator for Drain<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket,
+          P: Priority,
+{
+    type Item = (P, B::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let offset = match self.next_offset() {
+                Some(offset) => offset,
+                None => { self.bounds = None; return None; },
+            };
+
+            let priority = P::from_offset(offset);
+
+            if let Some(item) = self.remove(priority) {
+                return Some((priority, item));
+            }
+
+            self.exclude(offset);
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use std::error;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Error {
+    // More than one item was added to or removed from a DeferredBucket.
+    BucketConsumed,
+
+    // A DeferredBucket was initialized without a queue, e.g. with new_bucket.
+    DeferredInitialization,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Error::BucketConsumed => "You may only add or remove a single item from the bucket.",
+            Error::DeferredInitialization => "DeferredBucket should not be initialized this way.",
+        };
+
+        f.write_str(message)
+    }
+}
+
+impl error::Error for Error { }
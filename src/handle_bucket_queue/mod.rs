@@ -0,0 +1,198 @@
+use super::*;
+
+// Items are stored in slots and the buckets contain slot numbers. Each entry
+// records its position in its bucket so that it can be removed in constant
+// time by swapping it with the last slot number in the bucket.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slot: usize,
+    generation: usize,
+}
+
+struct Entry<T, P> {
+    item: T,
+    priority: P,
+    position: usize,
+}
+
+// The generation is incremented when a slot is freed so that stale handles
+// don't refer to items that are later inserted into the same slot.
+struct Slot<T, P> {
+    entry: Option<Entry<T, P>>,
+    generation: usize,
+}
+
+pub struct HandleBucketQueue<T, I: Index = SimpleIndex, P: Priority = usize> {
+    queue: BucketQueue<Vec<usize>, I, P>,
+    slots: Vec<Slot<T, P>>,
+    free: Vec<usize>,
+}
+
+impl<T, I: Index, P: Priority> HandleBucketQueue<T, I, P> {
+    pub fn new() -> Self {
+        Self { queue: BucketQueue::new(), slots: Vec::new(), free: Vec::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn min_priority(&self) -> Option<P> {
+        self.queue.min_priority()
+    }
+
+    pub fn max_priority(&self) -> Option<P> {
+        self.queue.max_priority()
+    }
+
+    pub fn insert(&mut self, item: T, priority: P) -> Handle {
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(Slot { entry: None, generation: 0 });
+                self.slots.len() - 1
+            },
+        };
+
+        let position = self.attach(slot, priority);
+        self.slots[slot].entry = Some(Entry { item, priority, position });
+
+        Handle { slot, generation: self.slots[slot].generation }
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.entry(handle).map(|entry| &entry.item)
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        self.entry(handle)?;
+        self.slots[handle.slot].entry.as_mut().map(|entry| &mut entry.item)
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.entry(handle).is_some()
+    }
+
+    pub fn priority_of(&self, handle: Handle) -> Option<P> {
+        self.entry(handle).map(|entry| entry.priority)
+    }
+
+    // Returns the previous priority or None if the handle is stale.
+    pub fn change_priority(&mut self, handle: Handle, priority: P) -> Option<P> {
+        let previous = self.priority_of(handle)?;
+
+        self.detach(handle.slot);
+        let position = self.attach(handle.slot, priority);
+
+        let entry = self.slots[handle.slot].entry.as_mut()?;
+        entry.priority = priority;
+        entry.position = position;
+
+        Some(previous)
+    }
+
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.entry(handle)?;
+        self.detach(handle.slot);
+
+        let slot = &mut self.slots[handle.slot];
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(handle.slot);
+
+        slot.entry.take().map(|entry| entry.item)
+    }
+
+    pub fn peek_min(&self) -> Option<(Handle, &T)> {
+        self.peek(self.min_priority()?)
+    }
+
+    pub fn peek_max(&self) -> Option<(Handle, &T)> {
+        self.peek(self.max_priority()?)
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.pop_min_with_priority().map(|(_, item)| item)
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        self.pop_max_with_priority().map(|(_, item)| item)
+    }
+
+    pub fn pop_min_with_priority(&mut self) -> Option<(P, T)> {
+        let priority = self.min_priority()?;
+        let (handle, _) = self.peek(priority)?;
+
+        Some((priority, self.remove(handle)?))
+    }
+
+    pub fn pop_max_with_priority(&mut self) -> Option<(P, T)> {
+        let priority = self.max_priority()?;
+        let (handle, _) = self.peek(priority)?;
+
+        Some((priority, self.remove(handle)?))
+    }
+
+    pub fn clear(&mut self) {
+        for slot in 0..self.slots.len() {
+            let handle = Handle { slot, generation: self.slots[slot].generation };
+            self.remove(handle);
+        }
+    }
+
+    fn entry(&self, handle: Handle) -> Option<&Entry<T, P>> {
+        let slot = self.slots.get(handle.slot)?;
+
+        if slot.generation == handle.generation {
+            slot.entry.as_ref()
+        } else {
+            None
+        }
+    }
+
+    // Items with the same priority are removed from the end of their bucket,
+    // which is in no particular order due to swap removals.
+    fn peek(&self, priority: P) -> Option<(Handle, &T)> {
+        let slot = *self.queue.bucket_for_peeking(priority)?.last()?;
+        let handle = Handle { slot, generation: self.slots[slot].generation };
+
+        Some((handle, self.get(handle)?))
+    }
+
+    fn attach(&mut self, slot: usize, priority: P) -> usize {
+        let bucket = self.queue.bucket_for_adding(priority);
+        bucket.push(slot);
+
+        bucket.len() - 1
+    }
+
+    fn detach(&mut self, slot: usize) {
+        let (priority, position) = match &self.slots[slot].entry {
+            Some(entry) => (entry.priority, entry.position),
+            None => return,
+        };
+
+        let bucket = match self.queue.bucket_for_removing(priority) {
+            Some(bucket) => bucket,
+            None => return,
+        };
+
+        bucket.swap_remove(position);
+
+        if let Some(&moved) = bucket.get(position) {
+            if let Some(entry) = &mut self.slots[moved].entry {
+                entry.position = position;
+            }
+        }
+    }
+}
+
+impl<T, I: Index, P: Priority> Default for HandleBucketQueue<T, I, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
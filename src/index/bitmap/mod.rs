@@ -0,0 +1,132 @@
+use super::*;
+use std::mem::take;
+
+const BITS: usize = 64;
+
+pub struct BitmapIndex {
+    len: usize,
+    words: Vec<u64>,
+    summary: Vec<u64>,
+}
+
+impl Index for BitmapIndex {
+    fn new() -> Self {
+        Self { len: 0, words: Vec::new(), summary: Vec::new() }
+    }
+
+    fn add<B: Bucket>(&mut self, priority: usize, buckets: &Vec<Option<B>>) {
+        self.added_n(1, priority, buckets);
+    }
+
+    fn remove<B: Bucket>(&mut self, priority: usize, buckets: &Vec<Option<B>>) {
+        // This is called before the item is removed from the bucket.
+        let size = Self::size_of_bucket(priority, buckets);
+
+        if size > 0 {
+            self.len -= 1;
+        }
+
+        if size <= 1 {
+            self.unset(priority);
+        }
+    }
+
+    fn added_n<B: Bucket>(&mut self, n: usize, priority: usize, _: &Vec<Option<B>>) {
+        if n > 0 {
+            self.len += n;
+            self.set(priority);
+        }
+    }
+
+    fn removed_n<B: Bucket>(&mut self, n: usize, priority: usize, buckets: &Vec<Option<B>>) {
+        self.len = self.len.saturating_sub(n);
+
+        if Self::size_of_bucket(priority, buckets) == 0 {
+            self.unset(priority);
+        }
+    }
+
+    fn supports_rebase() -> bool {
+        true
+    }
+
+    fn rebase(&mut self, old_base: usize, new_base: usize) {
+        let words = take(&mut self.words);
+        self.summary.clear();
+
+        for (w, word) in words.into_iter().enumerate() {
+            let mut bits = word;
+
+            while bits != 0 {
+                let priority = w * BITS + bits.trailing_zeros() as usize;
+                self.set(priority + old_base - new_base);
+
+                bits &= bits - 1;
+            }
+        }
+    }
+
+    fn min(&self) -> Option<usize> {
+        let (s, summary) = self.summary.iter().enumerate().find(|(_, w)| **w != 0)?;
+        let w = s * BITS + summary.trailing_zeros() as usize;
+
+        Some(w * BITS + self.words[w].trailing_zeros() as usize)
+    }
+
+    fn max(&self) -> Option<usize> {
+        let (s, summary) = self.summary.iter().enumerate().rev().find(|(_, w)| **w != 0)?;
+        let w = s * BITS + Self::highest_bit(*summary);
+
+        Some(w * BITS + Self::highest_bit(self.words[w]))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl BitmapIndex {
+    fn set(&mut self, priority: usize) {
+        let (w, bit) = (priority / BITS, priority % BITS);
+        let (s, summary_bit) = (w / BITS, w % BITS);
+
+        if w >= self.words.len() {
+            self.words.resize(w + 1, 0);
+        }
+
+        if s >= self.summary.len() {
+            self.summary.resize(s + 1, 0);
+        }
+
+        self.words[w] |= 1 << bit;
+        self.summary[s] |= 1 << summary_bit;
+    }
+
+    fn unset(&mut self, priority: usize) {
+        let (w, bit) = (priority / BITS, priority % BITS);
+
+        if let Some(word) = self.words.get_mut(w) {
+            *word &= !(1 << bit);
+
+            if *word == 0 {
+                self.summary[w / BITS] &= !(1 << (w % BITS));
+            }
+        }
+    }
+
+    fn highest_bit(word: u64) -> usize {
+        BITS - 1 - word.leading_zeros() as usize
+    }
+
+    fn size_of_bucket<B: Bucket>(priority: usize, buckets: &[Option<B>]) -> usize {
+        if let Some(Some(bucket)) = buckets.get(priority) {
+            bucket.len_bucket()
+        } else {
+            0
+        }
+    }
+}
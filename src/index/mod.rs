@@ -1,7 +1,11 @@
+pub mod bitmap;
 pub mod simple;
 
 use super::*;
 
+// The buckets are passed as a &Vec rather than a slice so that indexes written
+// against earlier versions of this trait still compile.
+#[allow(clippy::ptr_arg)]
 pub trait Index {
     fn new() -> Self;
 
@@ -11,6 +15,18 @@ pub trait Index {
     fn added_n<B: Bucket>(&mut self, n: usize, priority: usize, buckets: &Vec<Option<B>>);
     fn removed_n<B: Bucket>(&mut self, n: usize, priority: usize, buckets: &Vec<Option<B>>);
 
+    // Whether the index can be rebased. If not, BucketQueue keeps its base at
+    // zero, which needs a bucket slot for every priority up to the max.
+    fn supports_rebase() -> bool {
+        false
+    }
+
+    // Called when the buckets are moved so that old_base is now new_base. This
+    // is only called if #supports_rebase returns true.
+    fn rebase(&mut self, _old_base: usize, _new_base: usize) {
+        panic!("Index::rebase must be implemented if Index::supports_rebase returns true.");
+    }
+
     fn min(&self) -> Option<usize>;
     fn max(&self) -> Option<usize>;
 
@@ -18,9 +18,13 @@ impl Index for SimpleIndex {
     }
 
     fn remove<B: Bucket>(&mut self, priority: usize, buckets: &Vec<Option<B>>) {
-        self.len = self.len.saturating_sub(1);
+        let size = Self::size_of_bucket(priority, buckets);
 
-        if Self::size_of_bucket(priority, buckets) == 1 {
+        if size > 0 {
+            self.len -= 1;
+        }
+
+        if size == 1 {
             self.set_new_min_and_max(priority, buckets);
         }
     }
@@ -40,6 +44,15 @@ impl Index for SimpleIndex {
         }
     }
 
+    fn supports_rebase() -> bool {
+        true
+    }
+
+    fn rebase(&mut self, old_base: usize, new_base: usize) {
+        self.min = self.min.map(|min| min + old_base - new_base);
+        self.max = self.max.map(|max| max + old_base - new_base);
+    }
+
     fn min(&self) -> Option<usize> {
         self.min
     }
@@ -67,7 +80,7 @@ impl SimpleIndex {
         })
     }
 
-    fn size_of_bucket<B: Bucket>(priority: usize, buckets: &Vec<Option<B>>) -> usize {
+    fn size_of_bucket<B: Bucket>(priority: usize, buckets: &[Option<B>]) -> usize {
         if let Some(Some(bucket)) = buckets.get(priority) {
             bucket.len_bucket()
         } else {
@@ -75,7 +88,7 @@ impl SimpleIndex {
         }
     }
 
-    fn bucket_is_empty<B: Bucket>(priority: usize, buckets: &Vec<Option<B>>) -> bool {
+    fn bucket_is_empty<B: Bucket>(priority: usize, buckets: &[Option<B>]) -> bool {
         if let Some(Some(bucket)) = buckets.get(priority) {
             bucket.is_empty_bucket()
         } else {
@@ -83,7 +96,7 @@ impl SimpleIndex {
         }
     }
 
-    fn set_new_min_and_max<B: Bucket>(&mut self, priority: usize, buckets: &Vec<Option<B>>) {
+    fn set_new_min_and_max<B: Bucket>(&mut self, priority: usize, buckets: &[Option<B>]) {
         if let (Some(min), Some(max)) = (self.min, self.max) {
             if priority == min {
                 self.min = Self::find_next_priority((min + 1)..=max, buckets);
@@ -95,7 +108,7 @@ impl SimpleIndex {
         }
     }
 
-    fn find_next_priority<I, B>(iter: I, buckets: &Vec<Option<B>>) -> Option<usize>
+    fn find_next_priority<I, B>(iter: I, buckets: &[Option<B>]) -> Option<usize>
         where I: Iterator<Item=usize>, B: Bucket
     {
         for i in iter {
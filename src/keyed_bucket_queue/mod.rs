@@ -0,0 +1,148 @@
+use super::*;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::mem::replace;
+
+// Keys are stored in the buckets of a BucketQueue and their values are stored
+// in a HashMap. Removing a key searches its bucket, so this works best when
+// buckets are small relative to the number of keys.
+
+pub struct KeyedBucketQueue<K, V, B = VecDeque<K>, I = SimpleIndex, P = usize>
+    where K: Hash + Eq + Clone,
+          B: KeyedBucket<Item=K>,
+          I: Index,
+          P: Priority,
+{
+    queue: BucketQueue<B, I, P>,
+    entries: HashMap<K, (V, P)>,
+}
+
+impl<K, V, B, I, P> KeyedBucketQueue<K, V, B, I, P>
+    where K: Hash + Eq + Clone,
+          B: KeyedBucket<Item=K>,
+          I: Index,
+          P: Priority,
+{
+    pub fn new() -> Self {
+        Self { queue: BucketQueue::new(), entries: HashMap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn min_priority(&self) -> Option<P> {
+        self.queue.min_priority()
+    }
+
+    pub fn max_priority(&self) -> Option<P> {
+        self.queue.max_priority()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    pub fn priority_of(&self, key: &K) -> Option<P> {
+        self.entries.get(key).map(|(_, priority)| *priority)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|(value, _)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.entries.get_mut(key).map(|(value, _)| value)
+    }
+
+    // Returns the previous value and priority if the key was already queued.
+    // The key keeps its place in its bucket if the priority is unchanged.
+    pub fn insert(&mut self, key: K, value: V, priority: P) -> Option<(V, P)> {
+        let previous = self.entries.insert(key.clone(), (value, priority));
+
+        match &previous {
+            Some((_, p)) if *p == priority => {},
+            Some((_, p)) => self.move_key(key, *p, priority),
+            None => self.queue.bucket_for_adding(priority).push_key(key),
+        }
+
+        previous
+    }
+
+    // Returns the previous priority or None if the key isn't queued.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let entry = self.entries.get_mut(key)?;
+        let previous = replace(&mut entry.1, priority);
+
+        if previous != priority {
+            self.move_key(key.clone(), previous, priority);
+        }
+
+        Some(previous)
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<(V, P)> {
+        let (value, priority) = self.entries.remove(key)?;
+        self.queue.bucket_for_removing(priority)?.remove_key(key);
+
+        Some((value, priority))
+    }
+
+    pub fn peek_min(&self) -> Option<(&K, &V, P)> {
+        self.peek(self.min_priority()?)
+    }
+
+    pub fn peek_max(&self) -> Option<(&K, &V, P)> {
+        self.peek(self.max_priority()?)
+    }
+
+    pub fn pop_min(&mut self) -> Option<(K, V, P)> {
+        self.pop(self.min_priority()?)
+    }
+
+    pub fn pop_max(&mut self) -> Option<(K, V, P)> {
+        self.pop(self.max_priority()?)
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.entries.clear();
+    }
+
+    fn peek(&self, priority: P) -> Option<(&K, &V, P)> {
+        let key = self.queue.bucket_for_peeking(priority)?.peek_key()?;
+        let (key, (value, _)) = self.entries.get_key_value(key)?;
+
+        Some((key, value, priority))
+    }
+
+    fn pop(&mut self, priority: P) -> Option<(K, V, P)> {
+        let key = self.queue.bucket_for_removing(priority)?.pop_key()?;
+        let (value, _) = self.entries.remove(&key)?;
+
+        Some((key, value, priority))
+    }
+
+    fn move_key(&mut self, key: K, from: P, to: P) {
+        if let Some(bucket) = self.queue.bucket_for_removing(from) {
+            bucket.remove_key(&key);
+        }
+
+        self.queue.bucket_for_adding(to).push_key(key);
+    }
+}
+
+impl<K, V, B, I, P> Default for KeyedBucketQueue<K, V, B, I, P>
+    where K: Hash + Eq + Clone,
+          B: KeyedBucket<Item=K>,
+          I: Index,
+          P: Priority,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,23 +1,55 @@
 pub mod bucket;
+pub mod error;
+pub mod priority;
+pub mod order;
 pub mod queue;
 pub mod index;
 pub mod deferred;
+pub mod drain;
 pub mod bucket_queue;
+pub mod cyclic_bucket_queue;
+pub mod sparse_bucket_queue;
+pub mod handle_bucket_queue;
+pub mod keyed_bucket_queue;
+pub mod bounded_bucket_queue;
+pub mod concurrent_bucket_queue;
 
 pub use self::bucket::Bucket;
+pub use self::bucket::appendable::AppendableBucket;
 pub use self::bucket::double_ended::DoubleEndedBucket;
 pub use self::bucket::first_in_first_out::FirstInFirstOutBucket;
+pub use self::bucket::iterable::IterableBucket;
+pub use self::bucket::keyed::KeyedBucket;
 pub use self::bucket::last_in_first_out::LastInFirstOutBucket;
+pub use self::bucket::reservable::ReservableBucket;
+pub use self::bucket::retainable::RetainableBucket;
+
+pub use self::error::Error;
+
+pub use self::priority::Priority;
+
+pub use self::order::{Max, Min, Order};
 
 pub use self::queue::Queue;
 pub use self::queue::double_ended::DoubleEndedQueue;
 pub use self::queue::first_in_first_out::FirstInFirstOutQueue;
 pub use self::queue::last_in_first_out::LastInFirstOutQueue;
+pub use self::queue::lexicographic::LexicographicQueue;
 
 pub use self::index::Index;
+pub use self::index::bitmap::BitmapIndex;
 pub use self::index::simple::SimpleIndex;
 
 pub use self::deferred::Deferred;
 pub use self::deferred::bucket::DeferredBucket;
 
+pub use self::drain::Drain;
+
 pub use self::bucket_queue::BucketQueue;
+pub use self::bucket_queue::iter::{Iter, IterBuckets, IterMut};
+pub use self::cyclic_bucket_queue::CyclicBucketQueue;
+pub use self::sparse_bucket_queue::SparseBucketQueue;
+pub use self::handle_bucket_queue::{Handle, HandleBucketQueue};
+pub use self::keyed_bucket_queue::KeyedBucketQueue;
+pub use self::bounded_bucket_queue::{BoundedBucketQueue, Overflow};
+pub use self::concurrent_bucket_queue::ConcurrentBucketQueue;
@@ -0,0 +1,60 @@
+use super::*;
+
+// Orders choose which bucket of a queue to use next. A tuple of orders chooses
+// one per level of a nested queue, e.g. (Min, Max) minimises the outer priority
+// then maximises the inner one. Min and Max apply to all remaining levels.
+
+pub trait Order {
+    type Tail: Order;
+
+    fn next_priority<Q, B, P>(queue: &Q) -> Option<P>
+        where Q: Queue<B, P>, B: Bucket, P: Priority;
+}
+
+pub struct Min;
+pub struct Max;
+
+impl Order for Min {
+    type Tail = Min;
+
+    fn next_priority<Q, B, P>(queue: &Q) -> Option<P>
+        where Q: Queue<B, P>, B: Bucket, P: Priority
+    {
+        queue.min_priority()
+    }
+}
+
+impl Order for Max {
+    type Tail = Max;
+
+    fn next_priority<Q, B, P>(queue: &Q) -> Option<P>
+        where Q: Queue<B, P>, B: Bucket, P: Priority
+    {
+        queue.max_priority()
+    }
+}
+
+// ---------------------------------------------------------
+// Implement Order for tuples of orders, one for each level:
+// ---------------------------------------------------------
+
+macro_rules! tuple_order {
+    ($o:ident, $($os:ident),+) => (
+        #[allow(unused_parens)]
+        impl<$o: Order, $($os: Order),+> Order for ($o, $($os),+) {
+            type Tail = ($($os),+);
+
+            fn next_priority<Q, B, P>(queue: &Q) -> Option<P>
+                where Q: Queue<B, P>, B: Bucket, P: Priority
+            {
+                $o::next_priority(queue)
+            }
+        }
+    )
+}
+
+tuple_order!(O1, O2);
+tuple_order!(O1, O2, O3);
+tuple_order!(O1, O2, O3, O4);
+tuple_order!(O1, O2, O3, O4, O5);
+tuple_order!(O1, O2, O3, O4, O5, O6);
@@ -0,0 +1,57 @@
+// Priorities are mapped to a bucket offset. The mapping must preserve order so
+// that the minimum offset corresponds to the minimum priority and so on.
+
+pub trait Priority: Copy + Ord + Default {
+    fn to_offset(self) -> usize;
+
+    fn from_offset(offset: usize) -> Self;
+}
+
+// --------------------------------------------------------------
+// Provide implementations of Priority for unsigned integer types:
+// --------------------------------------------------------------
+
+macro_rules! unsigned_priority {
+    ($($t:ty),*) => ($(
+        impl Priority for $t {
+            fn to_offset(self) -> usize {
+                self as usize
+            }
+
+            fn from_offset(offset: usize) -> Self {
+                offset as $t
+            }
+        }
+    )*)
+}
+
+unsigned_priority!(u8, u16, u32, usize);
+
+#[cfg(target_pointer_width = "64")]
+unsigned_priority!(u64);
+
+// ------------------------------------------------------------
+// Provide implementations of Priority for signed integer types:
+// ------------------------------------------------------------
+
+// The sign bit is flipped so that negative priorities come before positive ones
+// when they are reinterpreted as an unsigned integer of the same width.
+
+macro_rules! signed_priority {
+    ($($t:ty => $u:ty),*) => ($(
+        impl Priority for $t {
+            fn to_offset(self) -> usize {
+                ((self as $u) ^ (1 << (<$u>::BITS - 1))) as usize
+            }
+
+            fn from_offset(offset: usize) -> Self {
+                ((offset as $u) ^ (1 << (<$u>::BITS - 1))) as $t
+            }
+        }
+    )*)
+}
+
+signed_priority!(i8 => u8, i16 => u16, i32 => u32, isize => usize);
+
+#[cfg(target_pointer_width = "64")]
+signed_priority!(i64 => u64);
@@ -1,19 +1,21 @@
 use super::*;
 
-pub trait DoubleEndedQueue<B: DoubleEndedBucket>: Queue<B> {
-    fn push_back(&mut self, item: B::Item, priority: usize) {
+use std::ops::RangeBounds;
+
+pub trait DoubleEndedQueue<B: DoubleEndedBucket, P: Priority = usize>: Queue<B, P> {
+    fn push_back(&mut self, item: B::Item, priority: P) {
         self.bucket_for_adding(priority).push_back(item);
     }
 
-    fn push_front(&mut self, item: B::Item, priority: usize) {
+    fn push_front(&mut self, item: B::Item, priority: P) {
         self.bucket_for_adding(priority).push_front(item);
     }
 
-    fn pop_back(&mut self, priority: usize) -> Option<B::Item> {
+    fn pop_back(&mut self, priority: P) -> Option<B::Item> {
         self.bucket_for_removing(priority)?.pop_back()
     }
 
-    fn pop_front(&mut self, priority: usize) -> Option<B::Item> {
+    fn pop_front(&mut self, priority: P) -> Option<B::Item> {
         self.bucket_for_removing(priority)?.pop_front()
     }
 
@@ -32,21 +34,202 @@ pub trait DoubleEndedQueue<B: DoubleEndedBucket>: Queue<B> {
     fn pop_front_max(&mut self) -> Option<B::Item> {
         self.pop_front(self.max_priority()?)
     }
+
+    fn pop_back_min_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.pop_back(priority)?))
+    }
+
+    fn pop_front_min_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.pop_front(priority)?))
+    }
+
+    fn pop_back_max_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.pop_back(priority)?))
+    }
+
+    fn pop_front_max_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.pop_front(priority)?))
+    }
+
+    fn drain_back_min(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, .., <B as DoubleEndedBucket>::pop_back)
+    }
+
+    fn drain_front_min(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, .., <B as DoubleEndedBucket>::pop_front)
+    }
+
+    fn drain_back_max(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::max_first(self, .., <B as DoubleEndedBucket>::pop_back)
+    }
+
+    fn drain_front_max(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::max_first(self, .., <B as DoubleEndedBucket>::pop_front)
+    }
+
+    fn drain_back_priority(&mut self, priority: P) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, priority..=priority, <B as DoubleEndedBucket>::pop_back)
+    }
+
+    fn drain_front_priority(&mut self, priority: P) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, priority..=priority, <B as DoubleEndedBucket>::pop_front)
+    }
+
+    fn drain_back_range<R: RangeBounds<P>>(&mut self, range: R) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, range, <B as DoubleEndedBucket>::pop_back)
+    }
+
+    fn drain_front_range<R: RangeBounds<P>>(&mut self, range: R) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, range, <B as DoubleEndedBucket>::pop_front)
+    }
+
+    fn peek_back<'a>(&'a self, priority: P) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking(priority)?.peek_back()
+    }
+
+    fn peek_front<'a>(&'a self, priority: P) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking(priority)?.peek_front()
+    }
+
+    fn peek_back_min<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek_back(self.min_priority()?)
+    }
+
+    fn peek_front_min<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek_front(self.min_priority()?)
+    }
+
+    fn peek_back_max<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek_back(self.max_priority()?)
+    }
+
+    fn peek_front_max<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek_front(self.max_priority()?)
+    }
+
+    fn peek_back_mut<'a>(&'a mut self, priority: P) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking_mut(priority)?.peek_back_mut()
+    }
+
+    fn peek_front_mut<'a>(&'a mut self, priority: P) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking_mut(priority)?.peek_front_mut()
+    }
+
+    fn peek_back_min_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_back_mut(self.min_priority()?)
+    }
+
+    fn peek_front_min_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_front_mut(self.min_priority()?)
+    }
+
+    fn peek_back_max_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_back_mut(self.max_priority()?)
+    }
+
+    fn peek_front_max_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_front_mut(self.max_priority()?)
+    }
 }
 
 // -----------------------------------------------------------------------
 // Implement DoubleEndedQueue for BucketQueues that use DoubleEndedBucket:
 // -----------------------------------------------------------------------
 
-impl<B: DoubleEndedBucket> DoubleEndedQueue<B> for BucketQueue<B> { }
+impl<B, I, P> DoubleEndedQueue<B, P> for BucketQueue<B, I, P>
+    where B: DoubleEndedBucket, I: Index, P: Priority { }
+
+
+// -----------------------------------------------------------------------------
+// Implement DoubleEndedQueue for CyclicBucketQueues that use DoubleEndedBucket:
+// -----------------------------------------------------------------------------
+
+impl<B: DoubleEndedBucket, P: Priority> DoubleEndedQueue<B, P> for CyclicBucketQueue<B, P> { }
+
+
+// -----------------------------------------------------------------------------
+// Implement DoubleEndedQueue for SparseBucketQueues that use DoubleEndedBucket:
+// -----------------------------------------------------------------------------
+
+impl<B: DoubleEndedBucket, P: Priority> DoubleEndedQueue<B, P> for SparseBucketQueue<B, P> {
+    fn pop_back(&mut self, priority: P) -> Option<B::Item> {
+        self.removing(priority, B::pop_back)
+    }
+
+    fn pop_front(&mut self, priority: P) -> Option<B::Item> {
+        self.removing(priority, B::pop_front)
+    }
+}
+
+
+// ------------------------------------------------------------------------------
+// Implement DoubleEndedQueue for BoundedBucketQueues that use DoubleEndedBucket:
+// ------------------------------------------------------------------------------
+
+impl<B, I, P> DoubleEndedQueue<B, P> for BoundedBucketQueue<B, I, P>
+    where B: DoubleEndedBucket, I: Index, P: Priority { }
 
 
 // ------------------------------------------------------------------
 // Implement DoubleEndedQueue for DeferredBucket to support deferral:
 // ------------------------------------------------------------------
 
-impl<'a, Q, B, C> DoubleEndedQueue<C> for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
-          B: Bucket + Queue<C>,
+impl<'a, Q, B, C, P, R> DoubleEndedQueue<C, R> for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket + DoubleEndedQueue<C, R>,
           C: DoubleEndedBucket,
-{ }
+          P: Priority,
+          R: Priority,
+{
+    fn pop_back(&mut self, priority: R) -> Option<C::Item> {
+        self.removing_with(|queue| queue.pop_back(priority))
+    }
+
+    fn pop_front(&mut self, priority: R) -> Option<C::Item> {
+        self.removing_with(|queue| queue.pop_front(priority))
+    }
+}
@@ -1,11 +1,13 @@
 use super::*;
 
-pub trait FirstInFirstOutQueue<B: FirstInFirstOutBucket>: Queue<B> {
-    fn enqueue(&mut self, item: B::Item, priority: usize) {
+use std::ops::RangeBounds;
+
+pub trait FirstInFirstOutQueue<B: FirstInFirstOutBucket, P: Priority = usize>: Queue<B, P> {
+    fn enqueue(&mut self, item: B::Item, priority: P) {
         self.bucket_for_adding(priority).enqueue(item);
     }
 
-    fn dequeue(&mut self, priority: usize) -> Option<B::Item> {
+    fn dequeue(&mut self, priority: P) -> Option<B::Item> {
         self.bucket_for_removing(priority)?.dequeue()
     }
 
@@ -16,21 +18,124 @@ pub trait FirstInFirstOutQueue<B: FirstInFirstOutBucket>: Queue<B> {
     fn dequeue_max(&mut self) -> Option<B::Item> {
         self.dequeue(self.max_priority()?)
     }
+
+    fn dequeue_min_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.dequeue(priority)?))
+    }
+
+    fn dequeue_max_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.dequeue(priority)?))
+    }
+
+    fn drain_min(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, .., <B as FirstInFirstOutBucket>::dequeue)
+    }
+
+    fn drain_max(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::max_first(self, .., <B as FirstInFirstOutBucket>::dequeue)
+    }
+
+    fn drain_priority(&mut self, priority: P) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, priority..=priority, <B as FirstInFirstOutBucket>::dequeue)
+    }
+
+    fn drain_range<R: RangeBounds<P>>(&mut self, range: R) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, range, <B as FirstInFirstOutBucket>::dequeue)
+    }
+
+    fn peek<'a>(&'a self, priority: P) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking(priority)?.peek()
+    }
+
+    fn peek_min<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek(self.min_priority()?)
+    }
+
+    fn peek_max<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek(self.max_priority()?)
+    }
+
+    fn peek_mut<'a>(&'a mut self, priority: P) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking_mut(priority)?.peek_mut()
+    }
+
+    fn peek_min_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_mut(self.min_priority()?)
+    }
+
+    fn peek_max_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_mut(self.max_priority()?)
+    }
 }
 
 // -------------------------------------------------------------------------------
 // Implement FirstInFirstOutQueue for BucketQueues that use FirstInFirstOutBucket:
 // -------------------------------------------------------------------------------
 
-impl<B: FirstInFirstOutBucket> FirstInFirstOutQueue<B> for BucketQueue<B> { }
+impl<B, I, P> FirstInFirstOutQueue<B, P> for BucketQueue<B, I, P>
+    where B: FirstInFirstOutBucket, I: Index, P: Priority { }
+
+
+// -------------------------------------------------------------------------------------
+// Implement FirstInFirstOutQueue for CyclicBucketQueues that use FirstInFirstOutBucket:
+// -------------------------------------------------------------------------------------
+
+impl<B: FirstInFirstOutBucket, P: Priority> FirstInFirstOutQueue<B, P> for CyclicBucketQueue<B, P> { }
+
+
+// -------------------------------------------------------------------------------------
+// Implement FirstInFirstOutQueue for SparseBucketQueues that use FirstInFirstOutBucket:
+// -------------------------------------------------------------------------------------
+
+impl<B: FirstInFirstOutBucket, P: Priority> FirstInFirstOutQueue<B, P> for SparseBucketQueue<B, P> {
+    fn dequeue(&mut self, priority: P) -> Option<B::Item> {
+        self.removing(priority, B::dequeue)
+    }
+}
+
+
+// --------------------------------------------------------------------------------------
+// Implement FirstInFirstOutQueue for BoundedBucketQueues that use FirstInFirstOutBucket:
+// --------------------------------------------------------------------------------------
+
+impl<B, I, P> FirstInFirstOutQueue<B, P> for BoundedBucketQueue<B, I, P>
+    where B: FirstInFirstOutBucket, I: Index, P: Priority { }
 
 
 // ----------------------------------------------------------------------
 // Implement FirstInFirstOutQueue for DeferredBucket to support deferral:
 // ----------------------------------------------------------------------
 
-impl<'a, Q, B, C> FirstInFirstOutQueue<C> for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
-          B: Bucket + Queue<C>,
+impl<'a, Q, B, C, P, R> FirstInFirstOutQueue<C, R> for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket + FirstInFirstOutQueue<C, R>,
           C: FirstInFirstOutBucket,
-{ }
+          P: Priority,
+          R: Priority,
+{
+    fn dequeue(&mut self, priority: R) -> Option<C::Item> {
+        self.removing_with(|queue| queue.dequeue(priority))
+    }
+}
@@ -1,11 +1,13 @@
 use super::*;
 
-pub trait LastInFirstOutQueue<B: LastInFirstOutBucket>: Queue<B> {
-    fn push(&mut self, item: B::Item, priority: usize) {
+use std::ops::RangeBounds;
+
+pub trait LastInFirstOutQueue<B: LastInFirstOutBucket, P: Priority = usize>: Queue<B, P> {
+    fn push(&mut self, item: B::Item, priority: P) {
         self.bucket_for_adding(priority).push(item);
     }
 
-    fn pop(&mut self, priority: usize) -> Option<B::Item> {
+    fn pop(&mut self, priority: P) -> Option<B::Item> {
         self.bucket_for_removing(priority)?.pop()
     }
 
@@ -16,21 +18,124 @@ pub trait LastInFirstOutQueue<B: LastInFirstOutBucket>: Queue<B> {
     fn pop_max(&mut self) -> Option<B::Item> {
         self.pop(self.max_priority()?)
     }
+
+    fn pop_min_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.pop(priority)?))
+    }
+
+    fn pop_max_with_priority(&mut self) -> Option<(P, B::Item)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.pop(priority)?))
+    }
+
+    fn drain_min(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, .., <B as LastInFirstOutBucket>::pop)
+    }
+
+    fn drain_max(&mut self) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::max_first(self, .., <B as LastInFirstOutBucket>::pop)
+    }
+
+    fn drain_priority(&mut self, priority: P) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, priority..=priority, <B as LastInFirstOutBucket>::pop)
+    }
+
+    fn drain_range<R: RangeBounds<P>>(&mut self, range: R) -> Drain<'_, Self, B, P>
+        where Self: Sized
+    {
+        Drain::min_first(self, range, <B as LastInFirstOutBucket>::pop)
+    }
+
+    fn peek<'a>(&'a self, priority: P) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking(priority)?.peek()
+    }
+
+    fn peek_min<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek(self.min_priority()?)
+    }
+
+    fn peek_max<'a>(&'a self) -> Option<&'a B::Item>
+        where B: 'a
+    {
+        self.peek(self.max_priority()?)
+    }
+
+    fn peek_mut<'a>(&'a mut self, priority: P) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.bucket_for_peeking_mut(priority)?.peek_mut()
+    }
+
+    fn peek_min_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_mut(self.min_priority()?)
+    }
+
+    fn peek_max_mut<'a>(&'a mut self) -> Option<&'a mut B::Item>
+        where B: 'a
+    {
+        self.peek_mut(self.max_priority()?)
+    }
 }
 
 // -----------------------------------------------------------------------------
 // Implement LastInFirstOutQueue for BucketQueues that use LastInFirstOutBucket:
 // -----------------------------------------------------------------------------
 
-impl<B: LastInFirstOutBucket> LastInFirstOutQueue<B> for BucketQueue<B> { }
+impl<B, I, P> LastInFirstOutQueue<B, P> for BucketQueue<B, I, P>
+    where B: LastInFirstOutBucket, I: Index, P: Priority { }
+
+
+// -----------------------------------------------------------------------------------
+// Implement LastInFirstOutQueue for CyclicBucketQueues that use LastInFirstOutBucket:
+// -----------------------------------------------------------------------------------
+
+impl<B: LastInFirstOutBucket, P: Priority> LastInFirstOutQueue<B, P> for CyclicBucketQueue<B, P> { }
+
+
+// -----------------------------------------------------------------------------------
+// Implement LastInFirstOutQueue for SparseBucketQueues that use LastInFirstOutBucket:
+// -----------------------------------------------------------------------------------
+
+impl<B: LastInFirstOutBucket, P: Priority> LastInFirstOutQueue<B, P> for SparseBucketQueue<B, P> {
+    fn pop(&mut self, priority: P) -> Option<B::Item> {
+        self.removing(priority, B::pop)
+    }
+}
+
+
+// ------------------------------------------------------------------------------------
+// Implement LastInFirstOutQueue for BoundedBucketQueues that use LastInFirstOutBucket:
+// ------------------------------------------------------------------------------------
+
+impl<B, I, P> LastInFirstOutQueue<B, P> for BoundedBucketQueue<B, I, P>
+    where B: LastInFirstOutBucket, I: Index, P: Priority { }
 
 
 // ---------------------------------------------------------------------
 // Implement LastInFirstOutQueue for DeferredBucket to support deferral:
 // ---------------------------------------------------------------------
 
-impl<'a, Q, B, C> LastInFirstOutQueue<C> for DeferredBucket<'a, Q, B>
-    where Q: Queue<B>,
-          B: Bucket + Queue<C>,
+impl<'a, Q, B, C, P, R> LastInFirstOutQueue<C, R> for DeferredBucket<'a, Q, B, P>
+    where Q: Queue<B, P>,
+          B: Bucket + LastInFirstOutQueue<C, R>,
           C: LastInFirstOutBucket,
-{ }
+          P: Priority,
+          R: Priority,
+{
+    fn pop(&mut self, priority: R) -> Option<C::Item> {
+        self.removing_with(|queue| queue.pop(priority))
+    }
+}
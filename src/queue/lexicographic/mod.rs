@@ -0,0 +1,156 @@
+use super::*;
+
+// Nested queues can be addressed with a tuple of priorities, one per level, e.g.
+// (1, 0, 2) is equivalent to queue.bucket(1).bucket(0).bucket(2). Priorities
+// are compared lexicographically, so the min is found level by level. The next
+// priorities can also mix min and max levels (see Order).
+
+pub trait LexicographicQueue<K>: Bucket {
+    type Leaf: Bucket<Item=Self::Item>;
+
+    fn next_priorities<O: Order>(&self) -> Option<K>;
+
+    fn leaf_for_adding(&mut self, priorities: K) -> &mut Self::Leaf;
+    fn leaf_for_removing(&mut self, priorities: K) -> Option<&mut Self::Leaf>;
+    fn leaf_for_peeking(&self, priorities: K) -> Option<&Self::Leaf>;
+
+    fn min_priorities(&self) -> Option<K> {
+        self.next_priorities::<Min>()
+    }
+
+    fn max_priorities(&self) -> Option<K> {
+        self.next_priorities::<Max>()
+    }
+
+    fn enqueue_at(&mut self, item: Self::Item, priorities: K)
+        where Self::Leaf: FirstInFirstOutBucket
+    {
+        self.leaf_for_adding(priorities).enqueue(item);
+    }
+
+    fn dequeue_min_at(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: FirstInFirstOutBucket, K: Copy
+    {
+        let priorities = self.min_priorities()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.dequeue()?))
+    }
+
+    fn dequeue_max_at(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: FirstInFirstOutBucket, K: Copy
+    {
+        let priorities = self.max_priorities()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.dequeue()?))
+    }
+
+    fn dequeue_next<O: Order>(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: FirstInFirstOutBucket, K: Copy
+    {
+        let priorities = self.next_priorities::<O>()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.dequeue()?))
+    }
+
+    fn push_at(&mut self, item: Self::Item, priorities: K)
+        where Self::Leaf: LastInFirstOutBucket
+    {
+        self.leaf_for_adding(priorities).push(item);
+    }
+
+    fn pop_min_at(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: LastInFirstOutBucket, K: Copy
+    {
+        let priorities = self.min_priorities()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.pop()?))
+    }
+
+    fn pop_max_at(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: LastInFirstOutBucket, K: Copy
+    {
+        let priorities = self.max_priorities()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.pop()?))
+    }
+
+    fn pop_next<O: Order>(&mut self) -> Option<(K, Self::Item)>
+        where Self::Leaf: LastInFirstOutBucket, K: Copy
+    {
+        let priorities = self.next_priorities::<O>()?;
+        Some((priorities, self.leaf_for_removing(priorities)?.pop()?))
+    }
+}
+
+// ---------------------------------------------------------------------
+// Implement LexicographicQueue for BucketQueues with a single priority:
+// ---------------------------------------------------------------------
+
+impl<B: Bucket, I: Index, P: Priority> LexicographicQueue<P> for BucketQueue<B, I, P> {
+    type Leaf = B;
+
+    fn next_priorities<O: Order>(&self) -> Option<P> {
+        O::next_priority(self)
+    }
+
+    fn leaf_for_adding(&mut self, priority: P) -> &mut B {
+        self.bucket_for_adding(priority)
+    }
+
+    fn leaf_for_removing(&mut self, priority: P) -> Option<&mut B> {
+        self.bucket_for_removing(priority)
+    }
+
+    fn leaf_for_peeking(&self, priority: P) -> Option<&B> {
+        self.bucket_for_peeking(priority)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Implement LexicographicQueue for nested BucketQueues with tuple priorities:
+// ---------------------------------------------------------------------------
+
+// Each level is updated in the same way as nested DeferredBuckets, i.e. every
+// queue on the path to the leaf bucket is told an item is being added/removed.
+
+macro_rules! lexicographic_queue {
+    ($p:ident $v:ident, $($ps:ident $vs:ident),+) => (
+        #[allow(unused_parens)]
+        impl<B, I, $p, $($ps),+> LexicographicQueue<($p, $($ps),+)> for BucketQueue<B, I, $p>
+            where B: LexicographicQueue<($($ps),+)>,
+                  I: Index,
+                  $p: Priority,
+                  $($ps: Priority),+
+        {
+            type Leaf = B::Leaf;
+
+            fn next_priorities<O: Order>(&self) -> Option<($p, $($ps),+)> {
+                let $v = O::next_priority(self)?;
+                let ($($vs),+) = self.bucket_for_peeking($v)?.next_priorities::<O::Tail>()?;
+
+                Some(($v, $($vs),+))
+            }
+
+            fn leaf_for_adding(&mut self, ($v, $($vs),+): ($p, $($ps),+)) -> &mut B::Leaf {
+                self.bucket_for_adding($v).leaf_for_adding(($($vs),+))
+            }
+
+            // Nothing is removed unless the leaf bucket has an item in it so that
+            // the queues on the path to it aren't told an item was removed.
+            fn leaf_for_removing(&mut self, ($v, $($vs),+): ($p, $($ps),+)) -> Option<&mut B::Leaf> {
+                let leaf = self.bucket_for_peeking($v)?.leaf_for_peeking(($($vs),+))?;
+
+                if leaf.is_empty_bucket() {
+                    return None;
+                }
+
+                self.bucket_for_removing($v)?.leaf_for_removing(($($vs),+))
+            }
+
+            fn leaf_for_peeking(&self, ($v, $($vs),+): ($p, $($ps),+)) -> Option<&B::Leaf> {
+                self.bucket_for_peeking($v)?.leaf_for_peeking(($($vs),+))
+            }
+        }
+    )
+}
+
+lexicographic_queue!(P1 p1, P2 p2);
+lexicographic_queue!(P1 p1, P2 p2, P3 p3);
+lexicographic_queue!(P1 p1, P2 p2, P3 p3, P4 p4);
+lexicographic_queue!(P1 p1, P2 p2, P3 p3, P4 p4, P5 p5);
+lexicographic_queue!(P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6);
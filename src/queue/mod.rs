@@ -1,24 +1,67 @@
 pub mod double_ended;
 pub mod first_in_first_out;
 pub mod last_in_first_out;
+pub mod lexicographic;
 
 use super::*;
 
-pub trait Queue<B: Bucket> {
+pub trait Queue<B: Bucket, P: Priority = usize> {
     fn new_queue() -> Self;
 
-    fn min_priority(&self) -> Option<usize>;
-    fn max_priority(&self) -> Option<usize>;
+    fn min_priority(&self) -> Option<P>;
+    fn max_priority(&self) -> Option<P>;
 
-    fn bucket_for_adding(&mut self, priority: usize) -> &mut B;
-    fn bucket_for_removing(&mut self, priority: usize) -> Option<&mut B>;
-    fn bucket_for_peeking(&self, priority: usize) -> Option<&B>;
-    fn bucket_for_replacing(&mut self, priority: usize) -> &mut Option<B>;
+    fn bucket_for_adding(&mut self, priority: P) -> &mut B;
+    fn bucket_for_removing(&mut self, priority: P) -> Option<&mut B>;
+    fn bucket_for_peeking(&self, priority: P) -> Option<&B>;
+    fn bucket_for_peeking_mut(&mut self, priority: P) -> Option<&mut B>;
+    fn bucket_for_replacing(&mut self, priority: P) -> &mut Option<B>;
 
-    fn items_replaced(&mut self, priority: usize, old_size: usize, new_size: usize);
+    fn items_replaced(&mut self, priority: P, old_size: usize, new_size: usize);
 
     fn len_queue(&self) -> usize;
     fn is_empty_queue(&self) -> bool;
 
-    fn replace(&mut self, priority: usize, replacement: Option<B>) -> Option<B>;
+    fn replace(&mut self, priority: P, replacement: Option<B>) -> Option<B>;
+
+    // The lowest non-empty priority that is at least `from`. This checks every
+    // bucket between the bounds of the queue, so sparse queues override it.
+    fn min_priority_from(&self, from: P) -> Option<P> {
+        let (min, max) = (self.min_priority()?, self.max_priority()?);
+
+        (from.max(min).to_offset()..=max.to_offset())
+            .map(P::from_offset)
+            .find(|&p| self.bucket_for_peeking(p).is_some_and(|b| !b.is_empty_bucket()))
+    }
+
+    // The highest non-empty priority that is at most `to`.
+    fn max_priority_to(&self, to: P) -> Option<P> {
+        let (min, max) = (self.min_priority()?, self.max_priority()?);
+
+        (min.to_offset()..=to.min(max).to_offset()).rev()
+            .map(P::from_offset)
+            .find(|&p| self.bucket_for_peeking(p).is_some_and(|b| !b.is_empty_bucket()))
+    }
+
+    fn take_min_bucket(&mut self) -> Option<(P, B)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.replace(priority, None)?))
+    }
+
+    fn take_max_bucket(&mut self) -> Option<(P, B)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.replace(priority, None)?))
+    }
+
+    // The recycled bucket (usually an empty one that was previously taken) is
+    // put in place of the bucket that's taken so that its allocation is reused.
+    fn swap_min_bucket(&mut self, recycled: B) -> Option<(P, B)> {
+        let priority = self.min_priority()?;
+        Some((priority, self.replace(priority, Some(recycled))?))
+    }
+
+    fn swap_max_bucket(&mut self, recycled: B) -> Option<(P, B)> {
+        let priority = self.max_priority()?;
+        Some((priority, self.replace(priority, Some(recycled))?))
+    }
 }
@@ -0,0 +1,180 @@
+use super::*;
+use std::collections::BTreeMap;
+use std::mem::replace;
+
+pub struct SparseBucketQueue<B: Bucket, P: Priority = usize> {
+    buckets: BTreeMap<P, Option<B>>,
+    len: usize,
+    pending: Option<P>,
+}
+
+impl<B: Bucket, P: Priority> SparseBucketQueue<B, P> {
+    pub fn new() -> Self {
+        Self { buckets: BTreeMap::new(), len: 0, pending: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The number of buckets currently stored in the BTreeMap.
+    pub fn len_buckets(&self) -> usize {
+        self.buckets.len()
+    }
+
+    // Removes an item with f and then removes the bucket if it is now empty.
+    // The pop methods of the queue traits are overridden to use this.
+    pub(crate) fn removing<F>(&mut self, priority: P, f: F) -> Option<B::Item>
+        where F: FnOnce(&mut B) -> Option<B::Item>
+    {
+        let item = f(self.bucket_for_removing(priority)?);
+        self.tidy();
+
+        item
+    }
+
+    // Buckets handed out by #bucket_for_removing are emptied after the call
+    // returns, so they're removed on the next call. The pop methods and
+    // DeferredBucket avoid this by removing them straight away.
+    fn tidy(&mut self) {
+        if let Some(priority) = self.pending.take() {
+            if self.size_of_bucket(priority) == 0 {
+                self.buckets.remove(&priority);
+            }
+        }
+    }
+
+    fn size_of_bucket(&self, priority: P) -> usize {
+        match self.buckets.get(&priority) {
+            Some(Some(bucket)) => bucket.len_bucket(),
+            _ => 0,
+        }
+    }
+
+    fn find_priority<'a, I>(mut iter: I) -> Option<P>
+        where I: Iterator<Item=(&'a P, &'a Option<B>)>, B: 'a, P: 'a
+    {
+        iter.find(|(_, b)| b.as_ref().is_some_and(|b| !b.is_empty_bucket()))
+            .map(|(priority, _)| *priority)
+    }
+}
+
+impl<B: Bucket, P: Priority> Default for SparseBucketQueue<B, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B: Bucket, P: Priority> Queue<B, P> for SparseBucketQueue<B, P> {
+    fn new_queue() -> Self {
+        Self::new()
+    }
+
+    fn min_priority(&self) -> Option<P> {
+        Self::find_priority(self.buckets.iter())
+    }
+
+    fn max_priority(&self) -> Option<P> {
+        Self::find_priority(self.buckets.iter().rev())
+    }
+
+    fn min_priority_from(&self, from: P) -> Option<P> {
+        Self::find_priority(self.buckets.range(from..))
+    }
+
+    fn max_priority_to(&self, to: P) -> Option<P> {
+        Self::find_priority(self.buckets.range(..=to).rev())
+    }
+
+    fn bucket_for_adding(&mut self, priority: P) -> &mut B {
+        self.tidy();
+        self.len += 1;
+
+        self.buckets.entry(priority).or_insert(None)
+            .get_or_insert_with(|| B::new_bucket())
+    }
+
+    fn bucket_for_removing(&mut self, priority: P) -> Option<&mut B> {
+        self.tidy();
+
+        if self.size_of_bucket(priority) > 0 {
+            self.len -= 1;
+            self.pending = Some(priority);
+        }
+
+        self.buckets.get_mut(&priority)?.as_mut()
+    }
+
+    fn bucket_for_peeking(&self, priority: P) -> Option<&B> {
+        self.buckets.get(&priority)?.as_ref()
+    }
+
+    fn bucket_for_peeking_mut(&mut self, priority: P) -> Option<&mut B> {
+        self.buckets.get_mut(&priority)?.as_mut()
+    }
+
+    fn bucket_for_replacing(&mut self, priority: P) -> &mut Option<B> {
+        // The length is not automatically updated as there is no way to tell
+        // how many items will be replaced. Instead, #items_replaced must be called.
+
+        self.tidy();
+        self.pending = Some(priority);
+
+        self.buckets.entry(priority).or_insert(None)
+    }
+
+    fn items_replaced(&mut self, _: P, old_size: usize, new_size: usize) {
+        self.len = (self.len + new_size).saturating_sub(old_size);
+        self.tidy();
+    }
+
+    fn len_queue(&self) -> usize {
+        self.len()
+    }
+
+    fn is_empty_queue(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn replace(&mut self, priority: P, replacement: Option<B>) -> Option<B> {
+        if replacement.is_none() && !self.buckets.contains_key(&priority) {
+            return None;
+        }
+
+        let existing = self.bucket_for_replacing(priority);
+
+        let old_size = existing.as_ref().map_or(0, |b| b.len_bucket());
+        let new_size = replacement.as_ref().map_or(0, |b| b.len_bucket());
+
+        let replaced = replace(existing, replacement);
+        self.items_replaced(priority, old_size, new_size);
+
+        replaced
+    }
+}
+
+impl<T, B: Bucket<Item=T>, P: Priority> Bucket for SparseBucketQueue<B, P> {
+    type Item = T;
+
+    fn new_bucket() -> Self {
+        Self::new()
+    }
+
+    fn len_bucket(&self) -> usize {
+        self.len()
+    }
+
+    fn is_empty_bucket(&self) -> bool {
+        self.is_empty()
+    }
+
+    fn clear(&mut self) {
+        self.buckets = BTreeMap::new();
+        self.len = 0;
+        self.pending = None;
+    }
+}
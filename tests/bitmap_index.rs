@@ -0,0 +1,129 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+
+type Buckets = Vec<Option<Vec<&'static str>>>;
+
+fn push(index: &mut BitmapIndex, buckets: &mut Buckets, item: &'static str, priority: usize) {
+    index.add(priority, buckets);
+
+    for _ in buckets.len()..=priority {
+        buckets.push(None);
+    }
+
+    buckets[priority].get_or_insert_with(Vec::new).push(item);
+}
+
+fn pop(index: &mut BitmapIndex, buckets: &mut Buckets, priority: usize) -> Option<&'static str> {
+    index.remove(priority, buckets);
+
+    buckets.get_mut(priority)?.as_mut()?.pop()
+}
+
+mod min_and_max {
+    use super::*;
+
+    #[test]
+    fn it_returns_none_when_nothing_has_been_added() {
+        let subject = BitmapIndex::new();
+
+        assert_eq!(subject.min(), None);
+        assert_eq!(subject.max(), None);
+    }
+
+    #[test]
+    fn it_finds_the_min_and_max_as_items_are_added_and_removed() {
+        let mut subject = BitmapIndex::new();
+        let mut buckets = Buckets::new();
+
+        push(&mut subject, &mut buckets, "first", 3);
+        push(&mut subject, &mut buckets, "second", 70);
+        push(&mut subject, &mut buckets, "third", 70);
+        push(&mut subject, &mut buckets, "fourth", 5_000);
+
+        assert_eq!(subject.min(), Some(3));
+        assert_eq!(subject.max(), Some(5_000));
+
+        pop(&mut subject, &mut buckets, 5_000);
+
+        assert_eq!(subject.min(), Some(3));
+        assert_eq!(subject.max(), Some(70));
+
+        pop(&mut subject, &mut buckets, 3);
+
+        assert_eq!(subject.min(), Some(70));
+        assert_eq!(subject.max(), Some(70));
+
+        pop(&mut subject, &mut buckets, 70);
+
+        assert_eq!(subject.min(), Some(70));
+        assert_eq!(subject.max(), Some(70));
+
+        pop(&mut subject, &mut buckets, 70);
+
+        assert_eq!(subject.min(), None);
+        assert_eq!(subject.max(), None);
+    }
+
+    #[test]
+    fn it_handles_priorities_on_word_boundaries() {
+        let mut subject = BitmapIndex::new();
+        let mut buckets = Buckets::new();
+
+        for &priority in &[0, 63, 64, 4095, 4096] {
+            push(&mut subject, &mut buckets, "item", priority);
+        }
+
+        let mut popped = vec![];
+
+        while let Some(min) = subject.min() {
+            pop(&mut subject, &mut buckets, min);
+            popped.push(min);
+        }
+
+        assert_eq!(popped, &[0, 63, 64, 4095, 4096]);
+    }
+}
+
+mod added_and_removed_n {
+    use super::*;
+
+    #[test]
+    fn it_updates_the_len_and_bounds_for_replaced_items() {
+        let mut subject = BitmapIndex::new();
+        let mut buckets = Buckets::new();
+
+        buckets.resize(10, None);
+        buckets[9] = Some(vec!["first", "second"]);
+        subject.added_n(2, 9, &buckets);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min(), Some(9));
+
+        buckets[9] = None;
+        subject.removed_n(2, 9, &buckets);
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min(), None);
+    }
+}
+
+mod len {
+    use super::*;
+
+    #[test]
+    fn it_does_not_count_removals_from_empty_buckets() {
+        let mut subject = BitmapIndex::new();
+        let mut buckets = Buckets::new();
+
+        push(&mut subject, &mut buckets, "first", 1);
+
+        assert_eq!(pop(&mut subject, &mut buckets, 0), None);
+        assert_eq!(pop(&mut subject, &mut buckets, 2), None);
+        assert_eq!(subject.len(), 1);
+
+        assert_eq!(pop(&mut subject, &mut buckets, 1), Some("first"));
+        assert_eq!(subject.len(), 0);
+    }
+}
@@ -0,0 +1,133 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+use std::collections::VecDeque;
+
+type Subject<B> = BoundedBucketQueue<B>;
+
+mod reject {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_item_when_the_queue_is_full() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(2, Overflow::Reject);
+
+        assert_eq!(subject.enqueue("first", 3), Ok(None));
+        assert_eq!(subject.enqueue("second", 5), Ok(None));
+        assert_eq!(subject.is_full(), true);
+
+        assert_eq!(subject.enqueue("third", 0), Err("third"));
+        assert_eq!(subject.len(), 2);
+
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.enqueue("third", 0), Ok(None));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+    }
+
+    #[test]
+    fn it_rejects_everything_when_the_capacity_is_zero() {
+        let mut subject = Subject::<Vec<usize>>::new(0, Overflow::EvictMax);
+
+        assert_eq!(subject.push(1, 1), Err(1));
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod evict_max {
+    use super::*;
+
+    #[test]
+    fn it_evicts_from_the_max_priority_bucket() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3, Overflow::EvictMax);
+
+        subject.enqueue("a", 1).unwrap();
+        subject.enqueue("b", 9).unwrap();
+        subject.enqueue("c", 9).unwrap();
+
+        assert_eq!(subject.enqueue("d", 0), Ok(Some("b")));
+        assert_eq!(subject.enqueue("e", 5), Ok(Some("c")));
+        assert_eq!(subject.enqueue("f", 5), Ok(Some("e")));
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.dequeue_min(), Some("d"));
+        assert_eq!(subject.dequeue_min(), Some("a"));
+        assert_eq!(subject.dequeue_min(), Some("f"));
+    }
+
+    #[test]
+    fn it_evicts_using_the_semantics_of_the_bucket() {
+        let mut subject = Subject::<Vec<&'static str>>::new(2, Overflow::EvictMax);
+
+        subject.push("a", 4).unwrap();
+        subject.push("b", 4).unwrap();
+
+        assert_eq!(subject.push("c", 1), Ok(Some("b")));
+        assert_eq!(subject.pop_max(), Some("a"));
+    }
+}
+
+mod evict_min {
+    use super::*;
+
+    #[test]
+    fn it_evicts_from_the_min_priority_bucket() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(2, Overflow::EvictMin);
+
+        subject.push_back("a", 1).unwrap();
+        subject.push_back("b", 1).unwrap();
+
+        assert_eq!(subject.push_back("c", 2), Ok(Some("a")));
+        assert_eq!(subject.push_front("d", 2), Ok(Some("b")));
+
+        assert_eq!(subject.min_priority(), Some(2));
+        assert_eq!(subject.pop_front_min(), Some("d"));
+        assert_eq!(subject.pop_front_min(), Some("c"));
+    }
+
+    #[test]
+    fn it_can_change_the_policy() {
+        let mut subject = Subject::<VecDeque<usize>>::new(1, Overflow::EvictMin);
+
+        subject.enqueue(1, 1).unwrap();
+        subject.set_overflow(Overflow::Reject);
+
+        assert_eq!(subject.overflow(), Overflow::Reject);
+        assert_eq!(subject.enqueue(2, 2), Err(2));
+    }
+}
+
+mod queue_traits {
+    use super::*;
+
+    #[test]
+    fn it_supports_the_removal_methods_of_the_queue_traits() {
+        let mut subject = Subject::<VecDeque<usize>>::new(10, Overflow::Reject);
+
+        for priority in 0..5 {
+            subject.enqueue(priority, priority).unwrap();
+        }
+
+        assert_eq!(subject.peek_max(), Some(&4));
+        assert_eq!(subject.dequeue_max_with_priority(), Some((4, 4)));
+        assert_eq!(subject.drain_range(1..3).count(), 2);
+        assert_eq!(subject.take_min_bucket().map(|(p, _)| p), Some(0));
+        assert_eq!(subject.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "BoundedBucketQueue is full.")]
+    fn it_panics_when_the_capacity_is_exceeded_through_the_trait() {
+        let mut subject = Subject::<VecDeque<usize>>::new(1, Overflow::EvictMin);
+
+        subject.enqueue(1, 1).unwrap();
+        FirstInFirstOutQueue::enqueue(&mut subject, 2, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "BoundedBucketQueue is full.")]
+    fn it_panics_when_a_bucket_is_replaced_with_too_many_items() {
+        let mut subject = Subject::<VecDeque<usize>>::new(2, Overflow::Reject);
+
+        subject.replace(1, Some(VecDeque::from(vec![1, 2, 3])));
+    }
+}
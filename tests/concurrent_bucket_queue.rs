@@ -0,0 +1,218 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+type Subject<T> = ConcurrentBucketQueue<T>;
+
+const THREADS: usize = 4;
+const ITEMS_PER_THREAD: usize = 5_000;
+
+mod first_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_minimum_priority() {
+        let subject = Subject::<&'static str>::new(10);
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 1);
+        subject.enqueue("third", 3);
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(3));
+
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min_with_priority(), Some((3, "third")));
+        assert_eq!(subject.dequeue_min(), None);
+
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_maximum_priority() {
+        let subject = Subject::<usize>::new(200);
+
+        subject.enqueue(1, 0);
+        subject.enqueue(2, 200);
+        subject.enqueue(3, 64);
+        subject.enqueue(4, 63);
+
+        assert_eq!(subject.dequeue_max_with_priority(), Some((200, 2)));
+        assert_eq!(subject.dequeue_max_with_priority(), Some((64, 3)));
+        assert_eq!(subject.dequeue_max_with_priority(), Some((63, 4)));
+        assert_eq!(subject.dequeue_max_with_priority(), Some((0, 1)));
+        assert_eq!(subject.dequeue_max(), None);
+    }
+
+    #[test]
+    fn it_can_dequeue_a_specific_priority() {
+        let subject = Subject::<usize>::new(5);
+
+        subject.enqueue(1, 2);
+
+        assert_eq!(subject.dequeue(3), None);
+        assert_eq!(subject.dequeue(6), None);
+        assert_eq!(subject.dequeue(2), Some(1));
+        assert_eq!(subject.dequeue(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Priority is greater than the max priority")]
+    fn it_panics_if_the_priority_is_too_large() {
+        let subject = Subject::<usize>::new(5);
+
+        subject.enqueue(1, 6);
+    }
+}
+
+mod stress {
+    use super::*;
+
+    // Items encode the thread that enqueued them and their sequence number.
+    fn item(thread: usize, n: usize) -> (usize, usize) {
+        (thread, n)
+    }
+
+    #[test]
+    fn it_does_not_lose_or_duplicate_items() {
+        let subject = Subject::<(usize, usize)>::new(100);
+        let dequeued = AtomicUsize::new(0);
+        let total = THREADS * ITEMS_PER_THREAD;
+
+        let mut results = thread::scope(|s| {
+            for t in 0..THREADS {
+                let subject = &subject;
+
+                s.spawn(move || {
+                    for n in 0..ITEMS_PER_THREAD {
+                        subject.enqueue(item(t, n), (n * 7 + t) % 101);
+                    }
+                });
+            }
+
+            let consumers = (0..THREADS).map(|_| s.spawn(|| {
+                let mut items = vec![];
+
+                while dequeued.load(Ordering::Acquire) < total {
+                    if let Some(item) = subject.dequeue_min() {
+                        dequeued.fetch_add(1, Ordering::AcqRel);
+                        items.push(item);
+                    } else {
+                        thread::yield_now();
+                    }
+                }
+
+                items
+            })).collect::<Vec<_>>();
+
+            consumers.into_iter().flat_map(|c| c.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        results.sort();
+
+        let expected = (0..THREADS)
+            .flat_map(|t| (0..ITEMS_PER_THREAD).map(move |n| item(t, n)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(results, expected);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_preserves_first_in_first_out_order_within_a_priority() {
+        let subject = Subject::<(usize, usize)>::new(3);
+
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let subject = &subject;
+
+                s.spawn(move || {
+                    for n in 0..ITEMS_PER_THREAD {
+                        subject.enqueue(item(t, n), n % 4);
+                    }
+                });
+            }
+        });
+
+        let mut last = vec![[None; 4]; THREADS];
+
+        while let Some((priority, (t, n))) = subject.dequeue_min_with_priority() {
+            assert_eq!(n % 4, priority);
+            assert_eq!(last[t][priority] < Some(n), true);
+
+            last[t][priority] = Some(n);
+        }
+
+        assert_eq!(subject.len(), 0);
+    }
+
+    #[test]
+    fn it_dequeues_in_priority_order_when_nothing_is_being_enqueued() {
+        let subject = Subject::<usize>::new(1000);
+
+        for n in 0..THREADS * ITEMS_PER_THREAD {
+            subject.enqueue(n, n * 31 % 1001);
+        }
+
+        let sequences = thread::scope(|s| {
+            let consumers = (0..THREADS).map(|_| s.spawn(|| {
+                let mut priorities = vec![];
+
+                while let Some((priority, _)) = subject.dequeue_min_with_priority() {
+                    priorities.push(priority);
+                }
+
+                priorities
+            })).collect::<Vec<_>>();
+
+            consumers.into_iter().map(|c| c.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        for priorities in &sequences {
+            assert_eq!(priorities.windows(2).all(|w| w[0] <= w[1]), true);
+        }
+
+        let count = sequences.iter().map(|p| p.len()).sum::<usize>();
+        assert_eq!(count, THREADS * ITEMS_PER_THREAD);
+    }
+
+    #[test]
+    fn it_keeps_the_length_consistent_with_the_items_in_the_queue() {
+        let subject = Subject::<usize>::new(63);
+        let dequeued = AtomicUsize::new(0);
+
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let (subject, dequeued) = (&subject, &dequeued);
+
+                s.spawn(move || {
+                    for n in 0..ITEMS_PER_THREAD {
+                        subject.enqueue(n, (n + t) % 64);
+
+                        if n % 3 == 0 && subject.dequeue_max().is_some() {
+                            dequeued.fetch_add(1, Ordering::AcqRel);
+                        }
+                    }
+                });
+            }
+        });
+
+        let expected = THREADS * ITEMS_PER_THREAD - dequeued.load(Ordering::Acquire);
+        assert_eq!(subject.len(), expected);
+
+        let mut remaining = 0;
+
+        while subject.dequeue_min().is_some() {
+            remaining += 1;
+        }
+
+        assert_eq!(remaining, expected);
+        assert_eq!(subject.len(), 0);
+    }
+}
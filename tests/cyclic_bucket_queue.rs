@@ -0,0 +1,312 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+use std::collections::VecDeque;
+
+type Subject<B> = CyclicBucketQueue<B>;
+
+mod first_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_minimum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3);
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", 3);
+        subject.enqueue("third", 0);
+
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+
+        subject.enqueue("fourth", 5);
+        subject.enqueue("fifth", 6);
+
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+        assert_eq!(subject.dequeue_min(), Some("fifth"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+
+    #[test]
+    fn it_can_dequeue_with_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3);
+
+        subject.enqueue("first", 10);
+        subject.enqueue("second", 12);
+        subject.enqueue("third", 13);
+
+        assert_eq!(subject.dequeue_max(), Some("third"));
+        assert_eq!(subject.dequeue_max(), Some("second"));
+        assert_eq!(subject.dequeue_max(), Some("first"));
+        assert_eq!(subject.dequeue_max(), None);
+    }
+
+    #[test]
+    fn it_does_not_confuse_priorities_that_share_a_slot() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3);
+
+        subject.enqueue("first", 1);
+        assert_eq!(subject.dequeue(5), None);
+        assert_eq!(subject.len(), 1);
+
+        assert_eq!(subject.dequeue_min(), Some("first"));
+
+        subject.enqueue("second", 5);
+        assert_eq!(subject.dequeue(1), None);
+        assert_eq!(subject.dequeue(5), Some("second"));
+    }
+}
+
+mod last_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_push_and_pop_with_minimum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new(2);
+
+        subject.push("first", 4);
+        subject.push("second", 5);
+        subject.push("third", 4);
+
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("first"));
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+}
+
+mod double_ended {
+    use super::*;
+
+    #[test]
+    fn it_can_pop_front_and_back_with_minimum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(1);
+
+        subject.push_back("first", 7);
+        subject.push_front("second", 7);
+        subject.push_back("third", 8);
+
+        assert_eq!(subject.pop_back_min(), Some("first"));
+        assert_eq!(subject.pop_front_min(), Some("second"));
+        assert_eq!(subject.pop_front_min(), Some("third"));
+        assert_eq!(subject.pop_front_min(), None);
+    }
+}
+
+mod peek {
+    use super::*;
+
+    #[test]
+    fn it_can_peek_without_removing_items() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3);
+
+        subject.push_back("first", 1);
+        subject.push_back("second", 4);
+        subject.push_front("third", 4);
+
+        assert_eq!(subject.peek_front_min(), Some(&"first"));
+        assert_eq!(subject.peek_front_max(), Some(&"third"));
+        assert_eq!(subject.peek_back_max(), Some(&"second"));
+        assert_eq!(subject.peek_front(0), None);
+
+        *subject.peek_back_max_mut().unwrap() = "fourth";
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.pop_back_max(), Some("fourth"));
+    }
+
+    #[test]
+    fn it_can_pop_items_along_with_their_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new(3);
+
+        subject.push_back("first", 9);
+        subject.push_back("second", 7);
+
+        assert_eq!(subject.pop_front_min_with_priority(), Some((7, "second")));
+        assert_eq!(subject.pop_front_min_with_priority(), Some((9, "first")));
+        assert_eq!(subject.pop_front_min_with_priority(), None);
+    }
+}
+
+mod drain {
+    use super::*;
+
+    #[test]
+    fn it_can_drain_items_with_maximum_priority_first() {
+        let mut subject = Subject::<Vec<&'static str>>::new(3);
+
+        subject.push("first", 10);
+        subject.push("second", 12);
+        subject.push("third", 12);
+
+        let drained: Vec<_> = subject.drain_max().collect();
+
+        assert_eq!(drained, vec![(12, "third"), (12, "second"), (10, "first")]);
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod take_bucket {
+    use super::*;
+
+    #[test]
+    fn it_takes_the_bucket_with_minimum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new(3);
+
+        subject.push("first", 10);
+        subject.push("second", 10);
+        subject.push("third", 12);
+
+        assert_eq!(subject.take_min_bucket(), Some((10, vec!["first", "second"])));
+        assert_eq!(subject.min_priority(), Some(12));
+        assert_eq!(subject.len(), 1);
+
+        subject.push("fourth", 15);
+        assert_eq!(subject.take_max_bucket(), Some((15, vec!["fourth"])));
+    }
+}
+
+mod window {
+    use super::*;
+
+    #[test]
+    fn it_accepts_priorities_within_the_max_span() {
+        let mut subject = Subject::<Vec<&'static str>>::new(10);
+
+        subject.push("first", 100);
+
+        assert_eq!(subject.in_window(90), true);
+        assert_eq!(subject.in_window(110), true);
+        assert_eq!(subject.in_window(89), false);
+        assert_eq!(subject.in_window(111), false);
+
+        subject.push("second", 105);
+
+        assert_eq!(subject.in_window(95), true);
+        assert_eq!(subject.in_window(94), false);
+    }
+
+    #[test]
+    fn it_moves_the_window_when_the_queue_is_emptied() {
+        let mut subject = Subject::<Vec<&'static str>>::new(10);
+
+        subject.push("first", 100);
+        subject.pop_min();
+
+        subject.push("second", 1_000);
+        subject.push("third", 990);
+
+        assert_eq!(subject.min_priority(), Some(990));
+        assert_eq!(subject.max_priority(), Some(1_000));
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_a_priority_is_above_the_window() {
+        let mut subject = Subject::<Vec<&'static str>>::new(10);
+
+        subject.push("first", 100);
+        subject.push("second", 111);
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_panics_if_a_priority_is_below_the_window() {
+        let mut subject = Subject::<Vec<&'static str>>::new(10);
+
+        subject.push("first", 100);
+        subject.push("second", 89);
+    }
+}
+
+mod dijkstra {
+    use super::*;
+
+    fn distances<Q: FirstInFirstOutQueue<VecDeque<usize>>>(mut queue: Q) -> Vec<usize> {
+        let mut popped = vec![];
+
+        queue.enqueue(0, 0);
+
+        while let Some((distance, node)) = queue.dequeue_min_with_priority() {
+            popped.push(distance);
+
+            if node < 20 {
+                queue.enqueue(node + 1, distance + 3);
+                queue.enqueue(node + 2, distance + 4);
+            }
+        }
+
+        popped
+    }
+
+    #[test]
+    fn it_supports_priorities_that_rise_beyond_the_max_span() {
+        let expected = distances(BucketQueue::<VecDeque<usize>>::new());
+        let actual = distances(Subject::<VecDeque<usize>>::new(4));
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.last() > Some(&4), true);
+    }
+}
+
+mod replace {
+    use super::*;
+
+    #[test]
+    fn it_replaces_a_bucket_in_the_queue() {
+        let mut subject = Subject::<Vec<&'static str>>::new(5);
+
+        subject.push("first", 10);
+        subject.push("second", 12);
+
+        let replaced = subject.replace(10, Some(vec!["third", "fourth"]));
+        assert_eq!(replaced.unwrap(), &["first"]);
+        assert_eq!(subject.len(), 3);
+
+        subject.replace(10, None);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(12));
+
+        assert_eq!(subject.replace(100, None), None);
+    }
+}
+
+mod nested_bucket_queue {
+    use super::*;
+
+    #[test]
+    fn it_supports_bucket_queues_as_buckets() {
+        let mut subject = Subject::<BucketQueue<Vec<&'static str>>>::new(2);
+
+        subject.bucket(5).push("first", 1);
+        subject.bucket(5).push("second", 0);
+        subject.bucket(6).bucket(0).push("third");
+
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+    }
+}
+
+mod with_bucket {
+    use super::*;
+
+    #[test]
+    fn it_can_add_and_remove_many_items_at_once() {
+        let mut subject = Subject::<Vec<usize>>::new(10);
+
+        subject.push(1, 3);
+
+        subject.with_bucket(8, |bucket| bucket.extend(0..5));
+        assert_eq!(subject.len(), 6);
+        assert_eq!(subject.max_priority(), Some(8));
+
+        subject.with_bucket(3, |bucket| bucket.clear());
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priority(), Some(8));
+    }
+}
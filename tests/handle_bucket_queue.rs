@@ -0,0 +1,215 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+
+type Subject<T> = HandleBucketQueue<T>;
+
+mod insert {
+    use super::*;
+
+    #[test]
+    fn it_returns_a_handle_to_the_item() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        let second = subject.insert("second", 5);
+
+        assert_eq!(subject.get(first), Some(&"first"));
+        assert_eq!(subject.get(second), Some(&"second"));
+        assert_eq!(subject.priority_of(first), Some(3));
+        assert_eq!(subject.priority_of(second), Some(5));
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(5));
+    }
+
+    #[test]
+    fn it_can_modify_items_via_their_handle() {
+        let mut subject = Subject::<usize>::new();
+        let handle = subject.insert(10, 0);
+
+        *subject.get_mut(handle).unwrap() += 1;
+
+        assert_eq!(subject.pop_min(), Some(11));
+    }
+}
+
+mod change_priority {
+    use super::*;
+
+    #[test]
+    fn it_moves_the_item_to_the_new_priority() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        subject.insert("second", 5);
+
+        assert_eq!(subject.change_priority(first, 7), Some(3));
+
+        assert_eq!(subject.priority_of(first), Some(7));
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_can_decrease_the_priority_of_an_item() {
+        let mut subject = Subject::<&'static str>::new();
+
+        subject.insert("first", 3);
+        let second = subject.insert("second", 10);
+        subject.insert("third", 10);
+
+        assert_eq!(subject.change_priority(second, 1), Some(10));
+
+        assert_eq!(subject.pop_min_with_priority(), Some((1, "second")));
+        assert_eq!(subject.pop_min_with_priority(), Some((3, "first")));
+        assert_eq!(subject.pop_min_with_priority(), Some((10, "third")));
+        assert_eq!(subject.pop_min_with_priority(), None);
+    }
+
+    #[test]
+    fn it_keeps_other_handles_valid_when_items_move_within_a_bucket() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 2);
+        let second = subject.insert("second", 2);
+        let third = subject.insert("third", 2);
+
+        subject.change_priority(first, 4);
+        subject.change_priority(third, 6);
+
+        assert_eq!(subject.get(second), Some(&"second"));
+        assert_eq!(subject.remove(second), Some("second"));
+        assert_eq!(subject.remove(third), Some("third"));
+        assert_eq!(subject.remove(first), Some("first"));
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod remove {
+    use super::*;
+
+    #[test]
+    fn it_removes_the_item_from_the_queue() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        subject.insert("second", 5);
+
+        assert_eq!(subject.remove(first), Some("first"));
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(5));
+
+        assert_eq!(subject.contains(first), false);
+        assert_eq!(subject.get(first), None);
+        assert_eq!(subject.priority_of(first), None);
+        assert_eq!(subject.change_priority(first, 1), None);
+        assert_eq!(subject.remove(first), None);
+    }
+
+    #[test]
+    fn it_does_not_reuse_handles_for_new_items() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        subject.remove(first);
+
+        let second = subject.insert("second", 3);
+
+        assert_eq!(first == second, false);
+        assert_eq!(subject.get(first), None);
+        assert_eq!(subject.get(second), Some(&"second"));
+    }
+
+    #[test]
+    fn it_invalidates_all_handles_when_cleared() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        let second = subject.insert("second", 4);
+
+        subject.clear();
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.contains(first), false);
+        assert_eq!(subject.contains(second), false);
+    }
+}
+
+mod peek_and_pop {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_handle_of_the_next_item() {
+        let mut subject = Subject::<&'static str>::new();
+
+        let first = subject.insert("first", 3);
+        let second = subject.insert("second", 5);
+
+        assert_eq!(subject.peek_min(), Some((first, &"first")));
+        assert_eq!(subject.peek_max(), Some((second, &"second")));
+
+        assert_eq!(subject.pop_max(), Some("second"));
+        assert_eq!(subject.pop_max(), Some("first"));
+        assert_eq!(subject.pop_max(), None);
+        assert_eq!(subject.peek_min(), None);
+    }
+
+    #[test]
+    fn it_can_use_any_index_and_type_of_priority() {
+        let mut subject = HandleBucketQueue::<&'static str, BitmapIndex, i16>::new();
+
+        let first = subject.insert("first", -300);
+        subject.insert("second", 200);
+
+        subject.change_priority(first, 500);
+
+        assert_eq!(subject.pop_max_with_priority(), Some((500, "first")));
+        assert_eq!(subject.pop_max_with_priority(), Some((200, "second")));
+    }
+}
+
+mod dijkstra {
+    use super::*;
+
+    // Each node is connected to the next two nodes with an edge cost of 3 and 7
+    // respectively, so the priority of every node after node 1 is decreased.
+    fn shortest_distances(nodes: usize) -> Vec<usize> {
+        let mut distances = vec![usize::MAX; nodes];
+        let mut handles = vec![None; nodes];
+        let mut queue = Subject::<usize>::new();
+
+        distances[0] = 0;
+        handles[0] = Some(queue.insert(0, 0));
+
+        while let Some((distance, node)) = queue.pop_min_with_priority() {
+            for (next, cost) in [(node + 1, 3), (node + 2, 7)] {
+                if next >= nodes || distance + cost >= distances[next] {
+                    continue;
+                }
+
+                distances[next] = distance + cost;
+
+                match handles[next] {
+                    Some(handle) if queue.contains(handle) => {
+                        queue.change_priority(handle, distance + cost);
+                    },
+                    _ => handles[next] = Some(queue.insert(next, distance + cost)),
+                }
+            }
+        }
+
+        distances
+    }
+
+    #[test]
+    fn it_supports_decrease_key_without_duplicate_entries() {
+        assert_eq!(shortest_distances(7), vec![0, 3, 6, 9, 12, 15, 18]);
+    }
+}
@@ -0,0 +1,152 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+
+type Subject<K, V> = KeyedBucketQueue<K, V>;
+
+mod insert {
+    use super::*;
+
+    #[test]
+    fn it_queues_each_key_with_its_value_and_priority() {
+        let mut subject = Subject::<&'static str, usize>::new();
+
+        assert_eq!(subject.insert("first", 10, 3), None);
+        assert_eq!(subject.insert("second", 20, 5), None);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.contains_key(&"first"), true);
+        assert_eq!(subject.contains_key(&"third"), false);
+        assert_eq!(subject.get(&"second"), Some(&20));
+        assert_eq!(subject.priority_of(&"first"), Some(3));
+        assert_eq!(subject.priority_of(&"third"), None);
+    }
+
+    #[test]
+    fn it_moves_an_existing_key_to_its_new_priority() {
+        let mut subject = Subject::<&'static str, usize>::new();
+
+        subject.insert("first", 10, 3);
+        subject.insert("second", 20, 5);
+
+        assert_eq!(subject.insert("first", 30, 7), Some((10, 3)));
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        assert_eq!(subject.pop_min(), Some(("second", 20, 5)));
+        assert_eq!(subject.pop_min(), Some(("first", 30, 7)));
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_keeps_the_place_of_a_key_if_its_priority_is_unchanged() {
+        let mut subject = Subject::<&'static str, usize>::new();
+
+        subject.insert("first", 10, 3);
+        subject.insert("second", 20, 3);
+
+        assert_eq!(subject.insert("first", 30, 3), Some((10, 3)));
+
+        assert_eq!(subject.pop_min(), Some(("first", 30, 3)));
+        assert_eq!(subject.pop_min(), Some(("second", 20, 3)));
+    }
+}
+
+mod change_priority {
+    use super::*;
+
+    #[test]
+    fn it_moves_the_key_without_changing_its_value() {
+        let mut subject = Subject::<char, &'static str>::new();
+
+        subject.insert('a', "first", 8);
+        subject.insert('b', "second", 4);
+
+        assert_eq!(subject.change_priority(&'a', 2), Some(8));
+        assert_eq!(subject.change_priority(&'c', 2), None);
+
+        assert_eq!(subject.pop_min(), Some(('a', "first", 2)));
+        assert_eq!(subject.pop_min(), Some(('b', "second", 4)));
+    }
+}
+
+mod remove {
+    use super::*;
+
+    #[test]
+    fn it_removes_the_key_from_the_queue() {
+        let mut subject = Subject::<&'static str, usize>::new();
+
+        subject.insert("first", 10, 3);
+        subject.insert("second", 20, 5);
+        subject.insert("third", 30, 3);
+
+        assert_eq!(subject.remove(&"first"), Some((10, 3)));
+        assert_eq!(subject.remove(&"first"), None);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.contains_key(&"first"), false);
+
+        assert_eq!(subject.remove(&"third"), Some((30, 3)));
+        assert_eq!(subject.min_priority(), Some(5));
+    }
+
+    #[test]
+    fn it_can_be_cleared() {
+        let mut subject = Subject::<&'static str, usize>::new();
+
+        subject.insert("first", 10, 3);
+        subject.clear();
+
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.get(&"first"), None);
+    }
+}
+
+mod tie_breaking {
+    use super::*;
+
+    #[test]
+    fn it_is_first_in_first_out_by_default() {
+        let mut subject = Subject::<&'static str, ()>::new();
+
+        subject.insert("first", (), 1);
+        subject.insert("second", (), 1);
+        subject.insert("third", (), 1);
+
+        assert_eq!(subject.peek_min(), Some((&"first", &(), 1)));
+        assert_eq!(subject.pop_max(), Some(("first", (), 1)));
+        assert_eq!(subject.pop_max(), Some(("second", (), 1)));
+        assert_eq!(subject.pop_max(), Some(("third", (), 1)));
+    }
+
+    #[test]
+    fn it_is_last_in_first_out_with_vec_buckets() {
+        let mut subject = KeyedBucketQueue::<&'static str, (), Vec<&'static str>>::new();
+
+        subject.insert("first", (), 1);
+        subject.insert("second", (), 1);
+        subject.insert("third", (), 1);
+
+        assert_eq!(subject.peek_min(), Some((&"third", &(), 1)));
+        assert_eq!(subject.pop_min(), Some(("third", (), 1)));
+        assert_eq!(subject.pop_min(), Some(("second", (), 1)));
+        assert_eq!(subject.pop_min(), Some(("first", (), 1)));
+    }
+
+    #[test]
+    fn it_can_use_any_index_and_type_of_priority() {
+        type Subject = KeyedBucketQueue<u8, (), Vec<u8>, BitmapIndex, i32>;
+        let mut subject = Subject::new();
+
+        subject.insert(1, (), -500);
+        subject.insert(2, (), 500);
+        subject.insert(1, (), 1_000);
+
+        assert_eq!(subject.pop_min(), Some((2, (), 500)));
+        assert_eq!(subject.pop_min(), Some((1, (), 1_000)));
+    }
+}
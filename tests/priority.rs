@@ -0,0 +1,244 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+use std::collections::VecDeque;
+
+mod offsets {
+    use super::*;
+
+    fn assert_order_is_preserved<P: Priority + std::fmt::Debug>(priorities: &[P]) {
+        for pair in priorities.windows(2) {
+            assert_eq!(pair[0].to_offset() < pair[1].to_offset(), true);
+        }
+
+        for &priority in priorities {
+            assert_eq!(P::from_offset(priority.to_offset()), priority);
+        }
+    }
+
+    #[test]
+    fn it_preserves_the_order_of_unsigned_priorities() {
+        assert_order_is_preserved(&[0u8, 1, 200, u8::MAX]);
+        assert_order_is_preserved(&[0u16, 1, 300, u16::MAX]);
+        assert_order_is_preserved(&[0u32, 1, 70_000, u32::MAX]);
+        assert_order_is_preserved(&[0u64, 1, 1 << 40, u64::MAX]);
+        assert_order_is_preserved(&[0usize, 1, 1 << 40, usize::MAX]);
+    }
+
+    #[test]
+    fn it_preserves_the_order_of_signed_priorities() {
+        assert_order_is_preserved(&[i8::MIN, -1, 0, 1, i8::MAX]);
+        assert_order_is_preserved(&[i16::MIN, -1, 0, 1, i16::MAX]);
+        assert_order_is_preserved(&[i32::MIN, -1, 0, 1, i32::MAX]);
+        assert_order_is_preserved(&[i64::MIN, -1, 0, 1, i64::MAX]);
+        assert_order_is_preserved(&[isize::MIN, -1, 0, 1, isize::MAX]);
+    }
+}
+
+mod bucket_queues {
+    use super::*;
+
+    #[test]
+    fn it_can_use_small_unsigned_priorities() {
+        let mut subject = BucketQueue::<VecDeque<&'static str>, SimpleIndex, u8>::new();
+
+        subject.enqueue("first", 200u8);
+        subject.enqueue("second", 3);
+        subject.enqueue("third", u8::MAX);
+
+        assert_eq!(subject.min_priority(), Some(3u8));
+        assert_eq!(subject.max_priority(), Some(u8::MAX));
+
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+
+    #[test]
+    fn it_can_use_priorities_with_any_index() {
+        let mut subject = BucketQueue::<Vec<&'static str>, BitmapIndex, u16>::new();
+
+        subject.push("first", 1_000u16);
+        subject.push("second", 1_000);
+        subject.push("third", 10);
+
+        assert_eq!(subject.pop_max(), Some("second"));
+        assert_eq!(subject.pop_max(), Some("first"));
+        assert_eq!(subject.pop_max(), Some("third"));
+        assert_eq!(subject.pop_max(), None);
+    }
+
+    #[test]
+    fn it_can_nest_queues_with_different_types_of_priority() {
+        type Inner = BucketQueue<Vec<&'static str>, SimpleIndex, u8>;
+        let mut subject = BucketQueue::<Inner, SimpleIndex, u32>::new();
+
+        subject.bucket(70_000u32).push("first", 2u8);
+        subject.bucket(70_000).bucket(1).push("second");
+        subject.bucket(5).push("third", 9);
+
+        assert_eq!(subject.min_priority(), Some(5u32));
+        assert_eq!(subject.max_bucket().min_priority(), Some(1u8));
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+    }
+}
+
+mod cyclic_bucket_queues {
+    use super::*;
+
+    #[test]
+    fn it_can_use_small_unsigned_priorities() {
+        let mut subject = CyclicBucketQueue::<VecDeque<&'static str>, u16>::new(10);
+
+        subject.push_back("first", 500u16);
+        subject.push_back("second", 505);
+
+        assert_eq!(subject.in_window(511), false);
+        assert_eq!(subject.pop_front_max(), Some("second"));
+        assert_eq!(subject.pop_front_max(), Some("first"));
+    }
+}
+
+mod sparse_bucket_queues {
+    use super::*;
+
+    #[test]
+    fn it_can_use_wide_unsigned_priorities() {
+        let mut subject = SparseBucketQueue::<VecDeque<&'static str>, u64>::new();
+
+        subject.enqueue("first", u64::MAX);
+        subject.enqueue("second", 1 << 50);
+
+        assert_eq!(subject.min_priority(), Some(1 << 50));
+        assert_eq!(subject.dequeue_max(), Some("first"));
+        assert_eq!(subject.dequeue_max(), Some("second"));
+    }
+}
+
+mod signed_priorities {
+    use super::*;
+
+    fn cost_deltas<I: Index>() {
+        let mut subject = BucketQueue::<VecDeque<isize>, I, isize>::new();
+
+        for delta in (-50..=50).rev() {
+            subject.enqueue(delta, delta);
+        }
+
+        assert_eq!(subject.len(), 101);
+        assert_eq!(subject.min_priority(), Some(-50));
+        assert_eq!(subject.max_priority(), Some(50));
+
+        for delta in -50..=50 {
+            assert_eq!(subject.dequeue_min(), Some(delta));
+        }
+
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+    }
+
+    #[test]
+    fn it_orders_negative_priorities_before_positive_ones() {
+        cost_deltas::<SimpleIndex>();
+        cost_deltas::<BitmapIndex>();
+    }
+
+    #[test]
+    fn it_grows_storage_in_both_directions() {
+        let mut subject = BucketQueue::<Vec<&'static str>, SimpleIndex, i32>::new();
+
+        subject.push("first", 0);
+        subject.push("second", -1_000);
+        subject.push("third", 1_000);
+        subject.push("fourth", -100_000);
+
+        assert_eq!(subject.min_priority(), Some(-100_000));
+        assert_eq!(subject.max_priority(), Some(1_000));
+
+        assert_eq!(subject.pop(-1_000), Some("second"));
+        assert_eq!(subject.pop(-999), None);
+
+        assert_eq!(subject.pop_min(), Some("fourth"));
+        assert_eq!(subject.min_priority(), Some(0));
+
+        assert_eq!(subject.pop_max(), Some("third"));
+        assert_eq!(subject.max_priority(), Some(0));
+    }
+
+    #[test]
+    fn it_tracks_the_min_and_max_when_buckets_are_replaced() {
+        let mut subject = BucketQueue::<Vec<&'static str>, SimpleIndex, i8>::new();
+
+        subject.push("first", -3);
+        subject.replace(-100, Some(vec!["second"]));
+        subject.replace(100, Some(vec!["third"]));
+
+        assert_eq!(subject.min_priority(), Some(-100));
+        assert_eq!(subject.max_priority(), Some(100));
+
+        subject.replace(-100, None);
+        subject.replace(100, None);
+
+        assert_eq!(subject.min_priority(), Some(-3));
+        assert_eq!(subject.max_priority(), Some(-3));
+    }
+
+    #[test]
+    fn it_supports_nested_queues_with_signed_priorities() {
+        type Inner = BucketQueue<Vec<&'static str>, SimpleIndex, i16>;
+        let mut subject = BucketQueue::<Inner, SimpleIndex, i16>::new();
+
+        subject.bucket(-1).push("first", 5);
+        subject.bucket(-1).bucket(-5).push("second");
+        subject.bucket(1).push("third", -7);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.max_bucket().pop_max(), Some("third"));
+        assert_eq!(subject.max_bucket().pop_max(), None);
+    }
+
+    #[test]
+    fn it_adds_to_bucket_zero_if_there_are_no_buckets() {
+        let mut subject = BucketQueue::<Vec<&'static str>, SimpleIndex, i64>::new();
+
+        subject.min_bucket().push("first");
+
+        assert_eq!(subject.min_priority(), Some(0));
+    }
+
+    #[test]
+    fn it_iterates_over_the_highest_signed_priority() {
+        let mut subject = BucketQueue::<Vec<&'static str>, SimpleIndex, isize>::new();
+
+        subject.push("first", isize::MAX);
+        subject.push("second", isize::MAX - 1);
+
+        let items: Vec<_> = subject.iter().collect();
+        assert_eq!(items, vec![(isize::MAX - 1, &"second"), (isize::MAX, &"first")]);
+    }
+
+    #[test]
+    fn it_supports_signed_priorities_in_cyclic_and_sparse_queues() {
+        let mut cyclic = CyclicBucketQueue::<Vec<&'static str>, i32>::new(10);
+
+        cyclic.push("first", -5);
+        cyclic.push("second", 5);
+
+        assert_eq!(cyclic.in_window(-6), false);
+        assert_eq!(cyclic.pop_min(), Some("first"));
+
+        let mut sparse = SparseBucketQueue::<Vec<&'static str>, i64>::new();
+
+        sparse.push("first", i64::MAX);
+        sparse.push("second", i64::MIN);
+
+        assert_eq!(sparse.min_priority(), Some(i64::MIN));
+        assert_eq!(sparse.pop_max(), Some("first"));
+    }
+}
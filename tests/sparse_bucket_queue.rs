@@ -0,0 +1,392 @@
+extern crate bucket_queue;
+
+use bucket_queue::*;
+use std::collections::VecDeque;
+
+type Subject<B> = SparseBucketQueue<B>;
+
+const HUGE: usize = 1 << 40;
+
+mod first_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", HUGE);
+        subject.enqueue("third", 0);
+
+        assert_eq!(subject.dequeue(HUGE), Some("second"));
+        assert_eq!(subject.dequeue(HUGE), None);
+
+        assert_eq!(subject.dequeue(0), Some("first"));
+        assert_eq!(subject.dequeue(0), Some("third"));
+        assert_eq!(subject.dequeue(0), None);
+
+        assert_eq!(subject.dequeue(1), None);
+    }
+
+    #[test]
+    fn it_can_dequeue_with_minimum_and_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", HUGE);
+        subject.enqueue("second", 7);
+        subject.enqueue("third", HUGE * 2);
+        subject.enqueue("fourth", 7);
+
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_max(), Some("third"));
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+        assert_eq!(subject.dequeue_max(), Some("first"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+}
+
+mod last_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_pop_with_minimum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", HUGE);
+        subject.push("second", HUGE + 1);
+        subject.push("third", HUGE);
+
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("first"));
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+}
+
+mod double_ended {
+    use super::*;
+
+    #[test]
+    fn it_can_pop_front_and_back_with_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 3);
+        subject.push_front("second", HUGE);
+        subject.push_back("third", HUGE);
+
+        assert_eq!(subject.pop_back_max(), Some("third"));
+        assert_eq!(subject.pop_front_max(), Some("second"));
+        assert_eq!(subject.pop_front_max(), Some("first"));
+        assert_eq!(subject.pop_front_max(), None);
+    }
+}
+
+mod peek {
+    use super::*;
+
+    #[test]
+    fn it_can_peek_without_removing_items() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", HUGE);
+        subject.push("second", 3);
+        subject.push("third", 3);
+
+        assert_eq!(subject.peek_min(), Some(&"third"));
+        assert_eq!(subject.peek_max(), Some(&"first"));
+        assert_eq!(subject.peek(4), None);
+
+        *subject.peek_min_mut().unwrap() = "fourth";
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.len_buckets(), 2);
+        assert_eq!(subject.pop_min(), Some("fourth"));
+    }
+}
+
+mod drain {
+    use super::*;
+
+    #[test]
+    fn it_can_drain_a_range_of_far_apart_priorities() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", HUGE);
+        subject.enqueue("second", 3);
+        subject.enqueue("third", HUGE * 2);
+
+        let drained: Vec<_> = subject.drain_range(0..=HUGE).collect();
+
+        assert_eq!(drained, vec![(3, "second"), (HUGE, "first")]);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(HUGE * 2));
+    }
+
+    #[test]
+    fn it_skips_wide_gaps_when_an_item_is_below_the_range() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", HUGE * 2);
+
+        let drained: Vec<_> = subject.drain_range(4..=HUGE * 4).collect();
+
+        assert_eq!(drained, vec![(HUGE * 2, "second")]);
+        assert_eq!(subject.drain_range(4..).next(), None);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(0));
+    }
+}
+
+mod take_bucket {
+    use super::*;
+
+    #[test]
+    fn it_takes_the_bucket_with_maximum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", HUGE);
+        subject.push("second", 3);
+
+        assert_eq!(subject.take_max_bucket(), Some((HUGE, vec!["first"])));
+        assert_eq!(subject.max_priority(), Some(3));
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.len_buckets(), 1);
+    }
+}
+
+mod min_and_max_priority {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_min_and_max_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+
+        subject.push("first", 5);
+        subject.push("second", HUGE);
+
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(HUGE));
+
+        subject.pop(5);
+
+        assert_eq!(subject.min_priority(), Some(HUGE));
+        assert_eq!(subject.max_priority(), Some(HUGE));
+
+        subject.pop(HUGE);
+
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+    }
+}
+
+mod len {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_number_of_enqueued_items() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", HUGE);
+        assert_eq!(subject.len(), 2);
+
+        subject.pop(1);
+        assert_eq!(subject.len(), 2);
+
+        subject.pop_min();
+        subject.pop_min();
+        subject.pop_min();
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_removes_empty_buckets() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", HUGE);
+        subject.push("third", HUGE);
+        assert_eq!(subject.len_buckets(), 2);
+
+        subject.pop_min();
+        subject.pop_max();
+        assert_eq!(subject.len_buckets(), 1);
+
+        subject.pop_max();
+        assert_eq!(subject.len_buckets(), 0);
+    }
+
+    #[test]
+    fn it_removes_buckets_as_soon_as_they_are_emptied() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", HUGE);
+        subject.push_back("third", HUGE * 2);
+
+        assert_eq!(subject.dequeue(0), Some("first"));
+        assert_eq!(subject.len_buckets(), 2);
+
+        assert_eq!(subject.pop_front(HUGE), Some("second"));
+        assert_eq!(subject.len_buckets(), 1);
+
+        assert_eq!(subject.pop_back(HUGE * 2), Some("third"));
+        assert_eq!(subject.len_buckets(), 0);
+    }
+
+    #[test]
+    fn it_removes_buckets_emptied_via_deferrals() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", HUGE);
+        subject.bucket(HUGE).pop();
+
+        assert_eq!(subject.len_buckets(), 0);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_removes_nested_buckets_emptied_via_deferrals() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(HUGE).push("first", 0);
+        subject.bucket(HUGE).push("second", HUGE);
+        subject.bucket(0).push("third", 1);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.len_buckets(), 1);
+
+        assert_eq!(subject.max_bucket().pop_max(), Some("second"));
+        assert_eq!(subject.bucket_for_peeking(HUGE).unwrap().len_buckets(), 1);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.len_buckets(), 0);
+        assert_eq!(subject.len(), 0);
+    }}
+
+mod replace {
+    use super::*;
+
+    #[test]
+    fn it_replaces_a_bucket_in_the_queue() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", HUGE);
+        subject.push("second", 1);
+
+        let replaced = subject.replace(HUGE, Some(vec!["third", "fourth"]));
+
+        assert_eq!(replaced.unwrap(), &["first"]);
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.replace(1, None).unwrap(), &["second"]);
+        assert_eq!(subject.replace(2, None), None);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.len_buckets(), 1);
+    }
+}
+
+mod clear {
+    use super::*;
+
+    #[test]
+    fn it_removes_all_items_from_the_queue() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", HUGE);
+
+        subject.clear();
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.len_buckets(), 0);
+        assert_eq!(subject.pop_min(), None);
+    }
+}
+
+mod nested_bucket_queue {
+    use super::*;
+
+    #[test]
+    fn it_can_contain_bucket_queues() {
+        let mut subject = Subject::<BucketQueue<Vec<&'static str>>>::new();
+
+        subject.bucket(HUGE).push("first", 1);
+        subject.bucket(HUGE).push("second", 0);
+        subject.bucket(3).bucket(2).push("third");
+
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.max_bucket().pop_max(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+        assert_eq!(subject.len(), 0);
+    }
+
+    #[test]
+    fn it_can_be_used_as_a_bucket() {
+        let mut subject = BucketQueue::<Subject<VecDeque<&'static str>>>::new();
+
+        subject.bucket(1).enqueue("first", HUGE);
+        subject.bucket(1).enqueue("second", 0);
+        subject.bucket(0).bucket(HUGE).enqueue("third");
+
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.min_bucket().dequeue_max(), Some("third"));
+        assert_eq!(subject.min_bucket().dequeue_max(), Some("first"));
+        assert_eq!(subject.min_bucket().dequeue_max(), Some("second"));
+        assert_eq!(subject.min_bucket().dequeue_max(), None);
+    }
+
+    #[test]
+    fn it_supports_replacing_nested_buckets_via_deferrals() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(HUGE).push("first", 0);
+        subject.bucket(HUGE).push("second", 1);
+        subject.bucket(0).push("third", HUGE);
+
+        let bucket = subject.bucket(HUGE).replace(1, None);
+
+        assert_eq!(bucket.unwrap(), &["second"]);
+        assert_eq!(subject.len(), 2);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+    }
+}
+
+mod with_bucket {
+    use super::*;
+
+    #[test]
+    fn it_can_add_and_remove_many_items_at_once() {
+        let mut subject = Subject::<Subject<Vec<usize>>>::new();
+
+        subject.with_bucket(HUGE, |bucket| {
+            for item in 0..5 {
+                bucket.push(item, item);
+            }
+        });
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.len_buckets(), 1);
+        assert_eq!(subject.min_priority(), Some(HUGE));
+
+        subject.with_bucket(HUGE, |bucket| bucket.clear());
+        subject.with_bucket(1, |_| ());
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.len_buckets(), 0);
+        assert_eq!(subject.min_priority(), None);
+    }
+}
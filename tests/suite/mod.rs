@@ -0,0 +1,2447 @@
+mod double_ended {
+    use super::*;
+
+    #[test]
+    fn it_can_push_and_pop_front_and_back_with_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 1);
+        subject.push_front("third", 0);
+        subject.push_back("fourth", 1);
+        subject.push_front("fifth", 1);
+
+        // Current state of bucket queue:
+        //   0: third, first
+        //   1: fifth, second, fourth
+
+        assert_eq!(subject.pop_back(0), Some("first"));
+        assert_eq!(subject.pop_front(0), Some("third"));
+        assert_eq!(subject.pop_back(0), None);
+
+        assert_eq!(subject.pop_front(1), Some("fifth"));
+        assert_eq!(subject.pop_back(1), Some("fourth"));
+        assert_eq!(subject.pop_back(1), Some("second"));
+        assert_eq!(subject.pop_front(1), None);
+
+        assert_eq!(subject.pop_front(2), None);
+        assert_eq!(subject.pop_back(3), None);
+    }
+
+    #[test]
+    fn it_can_pop_front_and_back_with_minimum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 1);
+        subject.push_front("third", 0);
+        subject.push_back("fourth", 1);
+        subject.push_front("fifth", 1);
+
+        // Current state of bucket queue:
+        //   0: third, first
+        //   1: fifth, second, fourth
+
+        assert_eq!(subject.pop_front_min(), Some("third"));
+        assert_eq!(subject.pop_back_min(), Some("first"));
+        assert_eq!(subject.pop_front_min(), Some("fifth"));
+        assert_eq!(subject.pop_back_min(), Some("fourth"));
+        assert_eq!(subject.pop_back_min(), Some("second"));
+        assert_eq!(subject.pop_front_min(), None);
+    }
+
+    #[test]
+    fn it_can_pop_front_and_back_with_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 1);
+        subject.push_front("third", 0);
+        subject.push_back("fourth", 1);
+        subject.push_front("fifth", 1);
+
+        // Current state of bucket queue:
+        //   0: third, first
+        //   1: fifth, second, fourth
+
+        assert_eq!(subject.pop_front_max(), Some("fifth"));
+        assert_eq!(subject.pop_back_max(), Some("fourth"));
+        assert_eq!(subject.pop_front_max(), Some("second"));
+        assert_eq!(subject.pop_back_max(), Some("first"));
+        assert_eq!(subject.pop_back_max(), Some("third"));
+        assert_eq!(subject.pop_front_max(), None);
+    }
+
+    #[test]
+    fn it_can_set_the_priority_then_push_and_pop_front_and_back() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.bucket(0).push_back("first");
+        subject.bucket(1).push_back("second");
+        subject.bucket(0).push_front("third");
+        subject.bucket(1).push_back("fourth");
+        subject.bucket(1).push_front("fifth");
+
+        // Current state of bucket queue:
+        //   0: third, first
+        //   1: fifth, second, fourth
+
+        assert_eq!(subject.bucket(0).pop_back(), Some("first"));
+        assert_eq!(subject.bucket(0).pop_front(), Some("third"));
+        assert_eq!(subject.bucket(0).pop_back(), None);
+
+        assert_eq!(subject.bucket(1).pop_front(), Some("fifth"));
+        assert_eq!(subject.bucket(1).pop_back(), Some("fourth"));
+        assert_eq!(subject.bucket(1).pop_back(), Some("second"));
+        assert_eq!(subject.bucket(1).pop_front(), None);
+
+        assert_eq!(subject.bucket(2).pop_front(), None);
+        assert_eq!(subject.bucket(3).pop_back(), None);
+    }
+}
+
+mod first_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", 1);
+        subject.enqueue("third", 0);
+
+        assert_eq!(subject.dequeue(1), Some("second"));
+        assert_eq!(subject.dequeue(1), None);
+
+        assert_eq!(subject.dequeue(0), Some("first"));
+        assert_eq!(subject.dequeue(0), Some("third"));
+        assert_eq!(subject.dequeue(0), None);
+
+        assert_eq!(subject.dequeue(2), None);
+        assert_eq!(subject.dequeue(3), None);
+    }
+
+    #[test]
+    fn it_can_dequeue_with_minimum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", 1);
+        subject.enqueue("third", 0);
+
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+
+    #[test]
+    fn it_can_dequeue_with_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 0);
+        subject.enqueue("second", 1);
+        subject.enqueue("third", 0);
+
+        assert_eq!(subject.dequeue_max(), Some("second"));
+        assert_eq!(subject.dequeue_max(), Some("first"));
+        assert_eq!(subject.dequeue_max(), Some("third"));
+        assert_eq!(subject.dequeue_max(), None);
+    }
+
+    #[test]
+    fn it_can_set_the_priority_then_enqueue_and_dequeue() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.bucket(0).enqueue("first");
+        subject.bucket(1).enqueue("second");
+        subject.bucket(0).enqueue("third");
+
+        assert_eq!(subject.bucket(1).dequeue(), Some("second"));
+        assert_eq!(subject.bucket(1).dequeue(), None);
+
+        assert_eq!(subject.bucket(0).dequeue(), Some("first"));
+        assert_eq!(subject.bucket(0).dequeue(), Some("third"));
+        assert_eq!(subject.bucket(0).dequeue(), None);
+
+        assert_eq!(subject.bucket(2).dequeue(), None);
+        assert_eq!(subject.bucket(3).dequeue(), None);
+    }
+}
+
+mod last_in_first_out {
+    use super::*;
+
+    #[test]
+    fn it_can_push_and_pop_with_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        assert_eq!(subject.pop(1), Some("second"));
+        assert_eq!(subject.pop(1), None);
+
+        assert_eq!(subject.pop(0), Some("third"));
+        assert_eq!(subject.pop(0), Some("first"));
+        assert_eq!(subject.pop(0), None);
+
+        assert_eq!(subject.pop(2), None);
+        assert_eq!(subject.pop(3), None);
+    }
+
+    #[test]
+    fn it_can_pop_with_minimum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("first"));
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_can_pop_with_maximum_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        assert_eq!(subject.pop_max(), Some("second"));
+        assert_eq!(subject.pop_max(), Some("third"));
+        assert_eq!(subject.pop_max(), Some("first"));
+        assert_eq!(subject.pop_max(), None);
+    }
+
+    #[test]
+    fn it_can_set_the_priority_then_push_and_pop() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.bucket(0).push("first");
+        subject.bucket(1).push("second");
+        subject.bucket(0).push("third");
+
+        assert_eq!(subject.bucket(1).pop(), Some("second"));
+        assert_eq!(subject.bucket(1).pop(), None);
+
+        assert_eq!(subject.bucket(0).pop(), Some("third"));
+        assert_eq!(subject.bucket(0).pop(), Some("first"));
+        assert_eq!(subject.bucket(0).pop(), None);
+
+        assert_eq!(subject.bucket(2).pop(), None);
+        assert_eq!(subject.bucket(3).pop(), None);
+    }
+}
+
+mod min_and_max_priority {
+    use super::*;
+
+    #[test]
+    fn it_can_return_the_min_and_max_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 3);
+        subject.push("second", 5);
+        subject.push("third", 5);
+        subject.push("fourth", 6);
+        subject.push("fifth", 7);
+        subject.push("sixth", 7);
+
+        // first(3), second(5), third(5), fourth(6), fifth(7), sixth(7)
+
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        subject.pop_max();
+
+        // first(3), second(5), third(5), fourth(6), fifth(7)
+
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        subject.pop(5);
+
+        // first(3), second(5), fourth(6), fifth(7)
+
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        subject.pop_min();
+
+        // second(5), fourth(6), fifth(7)
+
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        subject.pop_max();
+
+        // second(5), fourth(6)
+
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(6));
+
+        subject.pop_max();
+
+        // second(5)
+
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(5));
+
+        subject.pop_min();
+
+        // <empty>
+
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+    }
+}
+
+mod len {
+    use super::*;
+
+    #[test]
+    fn it_returns_the_number_of_enqueued_items() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        assert_eq!(subject.len(), 0);
+
+        subject.push("first", 0);
+        assert_eq!(subject.len(), 1);
+
+        subject.push("second", 1);
+        assert_eq!(subject.len(), 2);
+
+        subject.pop_min();
+        assert_eq!(subject.len(), 1);
+
+        subject.pop_min();
+        assert_eq!(subject.len(), 0);
+
+        subject.pop_min();
+        assert_eq!(subject.len(), 0);
+    }
+
+    #[test]
+    fn it_is_unchanged_when_popping_from_an_empty_bucket() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 2);
+
+        assert_eq!(subject.pop(1), None);
+        assert_eq!(subject.len(), 2);
+    }
+}
+
+mod is_empty {
+    use super::*;
+
+    #[test]
+    fn it_returns_true_if_no_items_are_enqueued() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        assert_eq!(subject.is_empty(), true);
+
+        subject.push("first", 0);
+        assert_eq!(subject.is_empty(), false);
+
+        subject.push("second", 1);
+        assert_eq!(subject.is_empty(), false);
+
+        subject.pop_min();
+        assert_eq!(subject.is_empty(), false);
+
+        subject.pop_min();
+        assert_eq!(subject.is_empty(), true);
+
+        subject.pop_min();
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod clear {
+    use super::*;
+
+    #[test]
+    fn it_removes_all_items_from_the_bucket_queue() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        subject.clear();
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_can_clear_deferred_buckets() { // This is equivalent to replacing.
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        subject.bucket(0).clear();
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.is_empty(), false);
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+}
+
+mod replace {
+    use super::*;
+
+    #[test]
+    fn it_replaces_a_bucket_in_the_bucket_queue() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        let bucket = vec!["fourth"];
+        subject.replace(0, Some(bucket));
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.is_empty(), false);
+
+        assert_eq!(subject.pop_min(), Some("fourth"));
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_can_remove_a_bucket_by_replacing_it_with_none() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        subject.replace(0, None);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.is_empty(), false);
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_returns_the_bucket_of_replaced_items() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        let bucket = subject.replace(0, None);
+        assert_eq!(bucket.unwrap(), &["first", "third"]);
+
+        let bucket = subject.replace(0, None);
+        assert_eq!(bucket, None);
+    }
+
+    #[test]
+    fn it_can_replace_a_bucket_that_doesnt_exist_yet() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 1);
+        subject.push("third", 0);
+
+        let replacement = vec!["fourth"];
+        let replaced = subject.replace(3, Some(replacement));
+
+        assert_eq!(replaced, None);
+
+        assert_eq!(subject.len(), 4);
+        assert_eq!(subject.is_empty(), false);
+
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("first"));
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), Some("fourth"));
+        assert_eq!(subject.pop_min(), None);
+    }
+}
+
+mod deferrals {
+    use super::*;
+
+    #[test]
+    fn it_adds_to_bucket_zero_if_there_are_no_buckets() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.min_bucket().push("first");
+        subject.max_bucket().push("second");
+
+        assert_eq!(subject.min_priority(), Some(0));
+        assert_eq!(subject.max_priority(), Some(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "You may only add or remove a single item from the bucket.")]
+    fn it_prevents_adding_to_the_bucket_more_than_once() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+        let mut bucket = subject.bucket(0);
+
+        bucket.push("first");
+        bucket.push("second"); // The second push should panic.
+    }
+
+    #[test]
+    #[should_panic(expected = "You may only add or remove a single item from the bucket.")]
+    fn it_prevents_removing_from_the_bucket_more_than_once() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 0);
+
+        let mut bucket = subject.bucket(0);
+
+        bucket.pop();
+        bucket.pop(); // The second pop should panic.
+    }
+
+    #[test]
+    fn it_can_ask_questions_of_buckets_more_than_once() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+
+        let bucket = subject.bucket(0);
+
+        assert_eq!(bucket.len_bucket(), 1);
+        assert_eq!(bucket.is_empty_bucket(), false);
+        assert_eq!(bucket.len_bucket(), 1);
+    }
+
+    #[test]
+    fn it_returns_an_error_when_adding_to_the_bucket_more_than_once() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+        let mut bucket = subject.bucket(0);
+
+        assert_eq!(bucket.is_consumed(), false);
+        bucket.try_adding().unwrap().push("first");
+        assert_eq!(bucket.is_consumed(), true);
+
+        assert_eq!(bucket.try_adding().err(), Some(Error::BucketConsumed));
+        assert_eq!(bucket.try_removing().err(), Some(Error::BucketConsumed));
+
+        assert_eq!(subject.len(), 1);
+    }
+
+    #[test]
+    fn it_returns_an_error_when_removing_from_the_bucket_more_than_once() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 0);
+        subject.push("second", 0);
+
+        let mut bucket = subject.bucket(0);
+
+        assert_eq!(bucket.try_removing().unwrap().unwrap().pop(), Some("second"));
+        assert_eq!(bucket.try_removing().err(), Some(Error::BucketConsumed));
+        assert_eq!(bucket.try_adding().err(), Some(Error::BucketConsumed));
+
+        assert_eq!(subject.len(), 1);
+    }
+
+    #[test]
+    fn it_consumes_the_bucket_even_if_there_is_nothing_to_remove() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+        let mut bucket = subject.bucket(0);
+
+        assert_eq!(bucket.try_removing().unwrap().is_none(), true);
+        assert_eq!(bucket.try_removing().err(), Some(Error::BucketConsumed));
+    }
+
+    #[test]
+    fn it_returns_an_error_when_using_a_nested_bucket_of_a_consumed_bucket() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+        let mut bucket = subject.bucket(0);
+
+        bucket.try_bucket(1).unwrap().push("first");
+
+        assert_eq!(bucket.try_bucket(1).err(), Some(Error::BucketConsumed));
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.try_bucket(0).unwrap().pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_describes_errors_with_the_same_message_as_the_panics() {
+        assert_eq!(
+            Error::BucketConsumed.to_string(),
+            "You may only add or remove a single item from the bucket.",
+        );
+
+        assert_eq!(
+            Error::DeferredInitialization.to_string(),
+            "DeferredBucket should not be initialized this way.",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DeferredBucket should not be initialized this way.")]
+    fn it_cannot_initialize_a_deferred_bucket_without_a_queue() {
+        DeferredBucket::<Subject<Vec<usize>>, Vec<usize>>::new_bucket();
+    }
+
+    #[test]
+    #[should_panic(expected = "DeferredBucket should not be initialized this way.")]
+    fn it_cannot_initialize_a_deferred_queue_without_a_queue() {
+        <DeferredBucket<'_, Subject<Subject<Vec<usize>>>, Subject<Vec<usize>>> as Queue<Vec<usize>>>::new_queue();
+    }
+}
+
+mod nested_bucket_queue {
+    use super::*;
+
+    #[test]
+    fn it_supports_using_a_bucket_queue_as_a_type_of_bucket() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket_for_adding(0).push("first", 0);
+        subject.bucket_for_adding(0).push("second", 1);
+        subject.bucket_for_adding(1).push("third", 0);
+
+        assert_eq!(subject.len(), 3);
+
+        let first = subject.bucket_for_removing(0).unwrap().pop_min();
+        assert_eq!(first, Some("first"));
+        assert_eq!(subject.len(), 2);
+
+        let max = subject.max_priority().unwrap();
+        let bucket = subject.bucket_for_removing(max).unwrap();
+        assert_eq!(bucket.pop_max(), Some("third"));
+    }
+
+    #[test]
+    fn it_supports_accessing_nested_buckets_via_deferrals() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(0).push("first", 0);
+        subject.bucket(0).push("second", 1);
+        subject.bucket(1).bucket(0).push("third");
+
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.bucket(0).pop_min(), Some("first"));
+        assert_eq!(subject.len(), 2);
+
+        assert_eq!(subject.max_bucket().pop_max(), Some("third"));
+        assert_eq!(subject.len(), 1);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.len(), 0);
+    }
+
+    #[test]
+    fn it_supports_replacing_nested_buckets_via_deferrals() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(0).push("first", 0);
+        subject.bucket(0).push("second", 1);
+        subject.bucket(0).push("third", 1);
+        subject.bucket(1).push("fourth", 0);
+
+        let bucket = subject.bucket(0).replace(1, None);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(bucket.unwrap(), &["second", "third"]);
+
+        let bucket = subject.bucket(0).replace(1, None);
+        assert_eq!(bucket, None);
+
+        let replacement = vec!["fifth"];
+        subject.bucket(2).replace(2, Some(replacement));
+        assert_eq!(bucket, None);
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("fourth"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("fifth"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+    }
+
+    #[test]
+    fn it_can_be_arbitrarily_nested() {
+        let mut subject = Subject::<Subject<Subject<Vec<&'static str>>>>::new();
+
+        subject.bucket(0).bucket(1).push("first", 2);
+        subject.bucket(3).bucket(4).bucket(5).push("second");
+        subject.bucket(3).bucket(6).bucket(7).push("third");
+
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.max_priority(), Some(3));
+        assert_eq!(subject.min_bucket().min_priority(), Some(1));
+        assert_eq!(subject.max_bucket().min_bucket().max_priority(), Some(5));
+
+        assert_eq!(subject.max_bucket().min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.max_bucket().min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.max_bucket().min_bucket().pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_can_replace_arbitrarily_nested_buckets_via_deferrals() {
+        let mut subject = Subject::<Subject<Subject<Vec<&'static str>>>>::new();
+
+        subject.bucket(0).bucket(1).bucket(0).push("first");
+        subject.bucket(0).bucket(1).bucket(0).push("second");
+        subject.bucket(0).bucket(1).bucket(1).push("third");
+        subject.bucket(0).bucket(2).bucket(0).push("fourth");
+        subject.bucket(0).bucket(2).bucket(0).push("fifth");
+        subject.bucket(1).bucket(0).bucket(0).push("sixth");
+
+        let bucket = subject.bucket(0).bucket(1).replace(0, None);
+        assert_eq!(bucket.unwrap(), &["first", "second"]);
+        assert_eq!(subject.len(), 4);
+        assert_eq!(subject.bucket(0).len(), 3);
+
+        let bucket = subject.bucket(0).bucket(1).replace(0, None);
+        assert_eq!(bucket, None);
+
+        let mut replacement = Subject::<Vec<&'static str>>::new();
+        replacement.bucket(1).push("seventh");
+        replacement.bucket(2).push("eighth");
+
+        let bucket = subject.bucket(1).replace(0, Some(replacement)); // TODO switch args
+        assert_eq!(bucket.unwrap().pop_min(), Some("sixth"));
+
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), Some("fifth"));
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), Some("fourth"));
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), Some("seventh"));
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), Some("eighth"));
+        assert_eq!(subject.min_bucket().min_bucket().pop_min(), None);
+    }
+}
+
+mod priority_offset {
+    use super::*;
+
+    #[test]
+    fn it_supports_priorities_that_are_far_from_zero() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", usize::MAX - 1);
+        subject.push("second", usize::MAX - 3);
+        subject.push("third", usize::MAX);
+
+        assert_eq!(subject.min_priority(), Some(usize::MAX - 3));
+        assert_eq!(subject.max_priority(), Some(usize::MAX));
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), Some("first"));
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), None);
+    }
+
+    #[test]
+    fn it_can_add_priorities_below_the_current_minimum() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 1_000_050);
+        subject.enqueue("second", 1_000_000);
+        subject.enqueue("third", 1_000_100);
+        subject.enqueue("fourth", 999_999);
+
+        assert_eq!(subject.min_priority(), Some(999_999));
+        assert_eq!(subject.max_priority(), Some(1_000_100));
+
+        assert_eq!(subject.dequeue(1_000_000), Some("second"));
+        assert_eq!(subject.dequeue(999_998), None);
+        assert_eq!(subject.len(), 3);
+
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+
+    #[test]
+    fn it_keeps_working_as_the_range_of_priorities_moves_upwards() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        for priority in 0..1_000 {
+            subject.enqueue(priority, priority * 1_000);
+            subject.enqueue(priority, priority * 1_000 + 1);
+
+            assert_eq!(subject.dequeue_min(), Some(priority));
+            assert_eq!(subject.min_priority(), Some(priority * 1_000 + 1));
+            assert_eq!(subject.max_priority(), Some(priority * 1_000 + 1));
+
+            assert_eq!(subject.dequeue_min(), Some(priority));
+            assert_eq!(subject.is_empty(), true);
+        }
+    }
+
+    #[test]
+    fn it_recycles_buckets_rather_than_growing_downwards_when_empty() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 1_000_000);
+        assert_eq!(subject.pop_min(), Some("first"));
+
+        subject.push("second", 0);
+        assert_eq!(subject.len_buckets(), 1);
+
+    }
+
+    #[test]
+    fn it_recycles_buckets_when_an_empty_queue_is_extended_or_replaced() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 500);
+        subject.pop_min();
+
+        subject.extend(vec![("second", 30), ("third", 20)]);
+
+        assert_eq!(subject.len_buckets(), 11);
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("second"));
+
+        assert_eq!(subject.replace(10, Some(vec!["fourth"])), None);
+
+        assert_eq!(subject.len_buckets(), 1);
+        assert_eq!(subject.min_priority(), Some(10));
+    }
+
+    #[test]
+    fn it_can_replace_buckets_below_the_current_minimum() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 500);
+
+        assert_eq!(subject.replace(100, None), None);
+        assert_eq!(subject.replace(200, Some(vec!["second"])), None);
+
+        assert_eq!(subject.min_priority(), Some(200));
+        assert_eq!(subject.max_priority(), Some(500));
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_supports_nested_queues_with_offset_priorities() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(50).push("first", 70);
+        subject.bucket(40).push("second", 90);
+        subject.bucket(40).bucket(80).push("third");
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+        assert_eq!(subject.min_bucket().pop_min(), None);
+    }
+}
+
+mod peek {
+    use super::*;
+
+    #[test]
+    fn it_can_peek_at_the_next_item_to_be_dequeued() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        assert_eq!(subject.peek_min(), None);
+        assert_eq!(subject.peek_max(), None);
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+
+        assert_eq!(subject.peek(3), Some(&"first"));
+        assert_eq!(subject.peek(4), None);
+        assert_eq!(subject.peek_min(), Some(&"first"));
+        assert_eq!(subject.peek_max(), Some(&"second"));
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.peek_min(), Some(&"third"));
+    }
+
+    #[test]
+    fn it_can_peek_at_the_next_item_to_be_popped() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 3);
+        subject.push("second", 5);
+        subject.push("third", 3);
+
+        assert_eq!(subject.peek(5), Some(&"second"));
+        assert_eq!(subject.peek_min(), Some(&"third"));
+        assert_eq!(subject.peek_max(), Some(&"second"));
+
+        assert_eq!(subject.pop_max(), Some("second"));
+        assert_eq!(subject.peek_max(), Some(&"third"));
+    }
+
+    #[test]
+    fn it_can_peek_at_the_front_and_back_of_buckets() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 1);
+        subject.push_front("third", 0);
+        subject.push_back("fourth", 1);
+
+        assert_eq!(subject.peek_front(0), Some(&"third"));
+        assert_eq!(subject.peek_back(0), Some(&"first"));
+
+        assert_eq!(subject.peek_front_min(), Some(&"third"));
+        assert_eq!(subject.peek_back_min(), Some(&"first"));
+        assert_eq!(subject.peek_front_max(), Some(&"second"));
+        assert_eq!(subject.peek_back_max(), Some(&"fourth"));
+
+        assert_eq!(subject.peek_front(2), None);
+        assert_eq!(subject.len(), 4);
+    }
+
+    #[test]
+    fn it_can_modify_items_in_place() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        subject.push_back(10, 0);
+        subject.push_back(20, 0);
+        subject.push_back(30, 1);
+
+        *subject.peek_min_mut().unwrap() += 1;
+        *subject.peek_back_min_mut().unwrap() += 2;
+        *subject.peek_front_max_mut().unwrap() += 3;
+
+        assert_eq!(subject.peek_mut(5), None);
+
+        assert_eq!(subject.pop_front_min(), Some(11));
+        assert_eq!(subject.pop_front_min(), Some(22));
+        assert_eq!(subject.pop_front_min(), Some(33));
+    }
+
+    #[test]
+    fn it_can_peek_via_deferrals() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.bucket(2).push("first");
+        subject.bucket(2).push("second");
+
+        assert_eq!(subject.bucket(2).peek(), Some(&"second"));
+        assert_eq!(subject.bucket(3).peek(), None);
+
+        *subject.bucket(2).peek_mut().unwrap() = "third";
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.pop_min(), Some("third"));
+        assert_eq!(subject.pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_can_peek_into_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<VecDeque<&'static str>>>::new();
+
+        subject.bucket(3).enqueue("first", 7);
+        subject.bucket(3).enqueue("second", 2);
+        subject.bucket(1).bucket(4).push_back("third");
+
+        assert_eq!(subject.min_bucket().peek_min(), Some(&"third"));
+        assert_eq!(subject.max_bucket().peek_max(), Some(&"first"));
+        assert_eq!(subject.bucket(3).bucket(2).peek_front(), Some(&"second"));
+
+        *subject.max_bucket().peek_min_mut().unwrap() = "fourth";
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.max_bucket().dequeue_min(), Some("fourth"));
+    }
+}
+
+mod with_priority {
+    use super::*;
+
+    #[test]
+    fn it_can_dequeue_items_along_with_their_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+
+        assert_eq!(subject.dequeue_max_with_priority(), Some((5, "second")));
+        assert_eq!(subject.dequeue_min_with_priority(), Some((3, "first")));
+        assert_eq!(subject.dequeue_max_with_priority(), Some((3, "third")));
+        assert_eq!(subject.dequeue_min_with_priority(), None);
+        assert_eq!(subject.dequeue_max_with_priority(), None);
+    }
+
+    #[test]
+    fn it_can_pop_items_along_with_their_priority() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 3);
+        subject.push("second", 5);
+        subject.push("third", 3);
+
+        assert_eq!(subject.pop_min_with_priority(), Some((3, "third")));
+        assert_eq!(subject.pop_max_with_priority(), Some((5, "second")));
+        assert_eq!(subject.pop_max_with_priority(), Some((3, "first")));
+        assert_eq!(subject.pop_min_with_priority(), None);
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_can_pop_front_and_back_along_with_their_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 1);
+        subject.push_back("third", 0);
+        subject.push_back("fourth", 1);
+
+        assert_eq!(subject.pop_back_min_with_priority(), Some((0, "third")));
+        assert_eq!(subject.pop_front_max_with_priority(), Some((1, "second")));
+        assert_eq!(subject.pop_front_min_with_priority(), Some((0, "first")));
+        assert_eq!(subject.pop_back_max_with_priority(), Some((1, "fourth")));
+        assert_eq!(subject.pop_back_max_with_priority(), None);
+    }
+
+    #[test]
+    fn it_can_return_the_path_of_priorities_for_nested_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(4).push("first", 7);
+        subject.bucket(2).push("second", 9);
+        subject.bucket(2).push("third", 8);
+
+        assert_eq!(subject.min_bucket().priority(), 2);
+        assert_eq!(subject.bucket(6).priority(), 6);
+
+        let pop = |s: &mut Subject<Subject<Vec<&'static str>>>| {
+            s.min_bucket().with_priority(|b| b.pop_min_with_priority())
+        };
+
+        assert_eq!(pop(&mut subject), Some((2, (8, "third"))));
+        assert_eq!(pop(&mut subject), Some((2, (9, "second"))));
+        assert_eq!(pop(&mut subject), Some((4, (7, "first"))));
+        assert_eq!(pop(&mut subject), None);
+    }
+}
+
+mod drain {
+    use super::*;
+
+    #[test]
+    fn it_can_drain_items_with_minimum_priority_first() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 8);
+
+        // Current state of bucket queue:
+        //   3: first, third
+        //   5: second
+        //   8: fourth
+
+        let drained: Vec<_> = subject.drain_min().collect();
+
+        assert_eq!(drained, vec![(3, "first"), (3, "third"), (5, "second"), (8, "fourth")]);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_can_drain_items_with_maximum_priority_first() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 8);
+
+        // Current state of bucket queue:
+        //   3: first, third
+        //   5: second
+        //   8: fourth
+
+        let drained: Vec<_> = subject.drain_max().collect();
+
+        assert_eq!(drained, vec![(8, "fourth"), (5, "second"), (3, "first"), (3, "third")]);
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_stops_draining_an_unbounded_range_after_the_max_priority() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        subject.enqueue(1, 0);
+        subject.enqueue(2, 10);
+
+        assert_eq!(subject.drain_range(5..).collect::<Vec<_>>(), vec![(10, 2)]);
+        assert_eq!(subject.drain_range(5..).next(), None);
+        assert_eq!(subject.drain_range(..0).next(), None);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(0));
+        assert_eq!(subject.max_priority(), Some(0));
+    }
+
+    #[test]
+    fn it_can_drain_a_range_with_maximum_priority_first() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        subject.enqueue(1, 1);
+        subject.enqueue(2, 3);
+        subject.enqueue(3, 10);
+
+        let drained: Vec<_> = Drain::max_first(&mut subject, ..5, VecDeque::pop_front).collect();
+
+        assert_eq!(drained, vec![(3, 2), (1, 1)]);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(10));
+    }
+
+    #[test]
+    fn it_can_drain_a_single_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 8);
+
+        // Current state of bucket queue:
+        //   3: first, third
+        //   5: second
+        //   8: fourth
+
+        let drained: Vec<_> = subject.drain_priority(3).collect();
+
+        assert_eq!(drained, vec![(3, "first"), (3, "third")]);
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(5));
+
+        assert_eq!(subject.drain_priority(4).next(), None);
+        assert_eq!(subject.len(), 2);
+    }
+
+    #[test]
+    fn it_can_drain_a_range_of_priorities() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 8);
+        subject.enqueue("fifth", 1);
+
+        // Current state of bucket queue:
+        //   1: fifth
+        //   3: first, third
+        //   5: second
+        //   8: fourth
+
+        let drained: Vec<_> = subject.drain_range(2..8).collect();
+
+        assert_eq!(drained, vec![(3, "first"), (3, "third"), (5, "second")]);
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(8));
+
+        assert_eq!(subject.drain_range(2..2).next(), None);
+        assert_eq!(subject.drain_range(..=1).collect::<Vec<_>>(), vec![(1, "fifth")]);
+        assert_eq!(subject.drain_range(8..).collect::<Vec<_>>(), vec![(8, "fourth")]);
+    }
+
+    #[test]
+    fn it_keeps_the_index_consistent_if_dropped_early() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 8);
+
+        // Current state of bucket queue:
+        //   3: first, third
+        //   5: second
+        //   8: fourth
+
+        assert_eq!(subject.drain_min().take(2).count(), 2);
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(8));
+
+        assert_eq!(subject.drain_range(0..=10).next(), Some((5, "second")));
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(8));
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+    }
+
+    #[test]
+    fn it_drains_in_last_in_first_out_order() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 3);
+        subject.push("second", 5);
+        subject.push("third", 3);
+
+        let drained: Vec<_> = subject.drain_max().collect();
+        assert_eq!(drained, vec![(5, "second"), (3, "third"), (3, "first")]);
+
+        subject.push("fourth", 1);
+        subject.push("fifth", 1);
+
+        let drained: Vec<_> = subject.drain_priority(1).collect();
+        assert_eq!(drained, vec![(1, "fifth"), (1, "fourth")]);
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_drains_from_the_front_or_back_of_buckets() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_back("first", 0);
+        subject.push_back("second", 0);
+        subject.push_back("third", 2);
+        subject.push_back("fourth", 2);
+
+        let drained: Vec<_> = subject.drain_back_priority(0).collect();
+        assert_eq!(drained, vec![(0, "second"), (0, "first")]);
+
+        let drained: Vec<_> = subject.drain_front_range(1..).collect();
+        assert_eq!(drained, vec![(2, "third"), (2, "fourth")]);
+
+        subject.push_back("fifth", 4);
+        subject.push_back("sixth", 4);
+        subject.push_back("seventh", 6);
+
+        assert_eq!(subject.drain_back_max().next(), Some((6, "seventh")));
+        assert_eq!(subject.drain_front_max().next(), Some((4, "fifth")));
+        assert_eq!(subject.drain_back_min().next(), Some((4, "sixth")));
+        assert_eq!(subject.drain_front_min().next(), None);
+    }
+
+    #[test]
+    fn it_can_drain_nested_buckets() {
+        let mut subject = Subject::<Subject<VecDeque<&'static str>>>::new();
+
+        subject.bucket(1).enqueue("first", 3);
+        subject.bucket(1).enqueue("second", 2);
+        subject.bucket(1).enqueue("third", 3);
+        subject.bucket(4).enqueue("fourth", 0);
+
+        let drained: Vec<_> = subject.bucket(1).drain_min().collect();
+        assert_eq!(drained, vec![(2, "second"), (3, "first"), (3, "third")]);
+
+        subject.bucket(1).push_back("fifth", 6);
+        subject.bucket(1).push_back("sixth", 6);
+
+        let drained: Vec<_> = subject.bucket(1).drain_back_max().collect();
+        assert_eq!(drained, vec![(6, "sixth"), (6, "fifth")]);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(4));
+        assert_eq!(subject.bucket(1).is_empty(), true);
+    }
+
+    #[test]
+    fn it_can_drain_part_of_a_nested_bucket() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(2).push("first", 0);
+        subject.bucket(2).push("second", 5);
+        subject.bucket(2).push("third", 5);
+
+        let drained: Vec<_> = subject.min_bucket().drain_range(1..).collect();
+        assert_eq!(drained, vec![(5, "third"), (5, "second")]);
+
+        assert_eq!(subject.bucket(2).drain_priority(7).next(), None);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.bucket(2).pop_min(), Some("first"));
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod iter {
+    use super::*;
+
+    #[test]
+    fn it_iterates_over_items_in_ascending_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 5);
+        subject.enqueue("second", 2);
+        subject.enqueue("third", 5);
+        subject.enqueue("fourth", 9);
+
+        // Current state of bucket queue:
+        //   2: second
+        //   5: first, third
+        //   9: fourth
+
+        let items: Vec<_> = subject.iter().collect();
+
+        assert_eq!(items, vec![(2, &"second"), (5, &"first"), (5, &"third"), (9, &"fourth")]);
+        assert_eq!(subject.len(), 4);
+    }
+
+    #[test]
+    fn it_can_be_reversed_to_iterate_in_descending_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 5);
+        subject.enqueue("second", 2);
+        subject.enqueue("third", 5);
+        subject.enqueue("fourth", 9);
+
+        // Current state of bucket queue:
+        //   2: second
+        //   5: first, third
+        //   9: fourth
+
+        let items: Vec<_> = subject.iter().rev().collect();
+
+        assert_eq!(items, vec![(9, &"fourth"), (5, &"third"), (5, &"first"), (2, &"second")]);
+    }
+
+    #[test]
+    fn it_can_iterate_from_both_ends_at_once() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 5);
+        subject.enqueue("second", 2);
+        subject.enqueue("third", 5);
+        subject.enqueue("fourth", 9);
+
+        // Current state of bucket queue:
+        //   2: second
+        //   5: first, third
+        //   9: fourth
+
+        let mut iter = subject.iter();
+
+        assert_eq!(iter.next(), Some((2, &"second")));
+        assert_eq!(iter.next_back(), Some((9, &"fourth")));
+        assert_eq!(iter.next_back(), Some((5, &"third")));
+        assert_eq!(iter.next(), Some((5, &"first")));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn it_iterates_over_non_empty_buckets() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 5);
+        subject.enqueue("second", 2);
+        subject.enqueue("third", 5);
+        subject.enqueue("fourth", 9);
+
+        // Current state of bucket queue:
+        //   2: second
+        //   5: first, third
+        //   9: fourth
+
+        subject.dequeue(9);
+
+        let priorities: Vec<_> = subject.iter_buckets().map(|(p, b)| (p, b.len())).collect();
+        assert_eq!(priorities, vec![(2, 1), (5, 2)]);
+
+        let reversed: Vec<_> = subject.iter_buckets().rev().map(|(p, _)| p).collect();
+        assert_eq!(reversed, vec![5, 2]);
+    }
+
+    #[test]
+    fn it_can_modify_items_in_place() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        subject.push(1, 3);
+        subject.push(2, 3);
+        subject.push(3, 7);
+
+        for (priority, item) in subject.iter_mut() {
+            *item *= priority;
+        }
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.pop_max(), Some(21));
+        assert_eq!(subject.pop_max(), Some(6));
+        assert_eq!(subject.pop_max(), Some(3));
+    }
+
+    #[test]
+    fn it_returns_nothing_for_an_empty_queue() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 5);
+        subject.enqueue("second", 2);
+
+        subject.clear();
+
+        assert_eq!(subject.iter().next(), None);
+        assert_eq!(subject.iter_buckets().next(), None);
+        assert_eq!(subject.iter_mut().next_back(), None);
+    }
+
+    #[test]
+    fn it_supports_priorities_that_are_far_from_zero() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 1_000_000);
+        subject.push("second", 999_990);
+
+        let items: Vec<_> = subject.iter().collect();
+        assert_eq!(items, vec![(999_990, &"second"), (1_000_000, &"first")]);
+    }
+
+    #[test]
+    fn it_iterates_over_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 4);
+        subject.bucket(0).push("second", 9);
+        subject.bucket(1).push("third", 2);
+
+        let items: Vec<_> = subject.iter().collect();
+        assert_eq!(items, vec![(0, &"second"), (1, &"third"), (1, &"first")]);
+
+        for (_, item) in subject.iter_mut() {
+            *item = "changed";
+        }
+
+        assert_eq!(subject.min_bucket().pop_min(), Some("changed"));
+    }
+
+    #[test]
+    fn it_iterates_over_the_highest_possible_priority() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        subject.push(1, usize::MAX);
+        subject.push(2, usize::MAX - 1);
+
+        let items: Vec<_> = subject.iter().rev().collect();
+        assert_eq!(items, vec![(usize::MAX, &1), (usize::MAX - 1, &2)]);
+
+        for (_, item) in subject.iter_mut() {
+            *item += 10;
+        }
+
+        assert_eq!(subject.iter_buckets().count(), 2);
+        assert_eq!(subject.pop_max(), Some(11));
+    }
+}
+
+mod retain {
+    use super::*;
+
+    #[test]
+    fn it_keeps_only_the_items_that_match_the_predicate() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        for item in 0..10 {
+            subject.enqueue(item, item % 4);
+        }
+
+        // Current state of bucket queue:
+        //   0: 0, 4, 8
+        //   1: 1, 5, 9
+        //   2: 2, 6
+        //   3: 3, 7
+
+        subject.retain(|priority, &item| priority != 2 && item < 8);
+
+        assert_eq!(subject.len(), 6);
+        assert_eq!(subject.dequeue(0), Some(0));
+        assert_eq!(subject.dequeue(0), Some(4));
+        assert_eq!(subject.dequeue(0), None);
+        assert_eq!(subject.dequeue(2), None);
+        assert_eq!(subject.dequeue(3), Some(3));
+    }
+
+    #[test]
+    fn it_updates_the_min_and_max_priority() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        for item in 0..10 {
+            subject.enqueue(item, item % 4);
+        }
+
+        // Current state of bucket queue:
+        //   0: 0, 4, 8
+        //   1: 1, 5, 9
+        //   2: 2, 6
+        //   3: 3, 7
+
+        subject.retain(|priority, _| priority == 1 || priority == 2);
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(2));
+
+        subject.retain(|_, _| false);
+
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+    }
+
+    #[test]
+    fn it_removes_the_first_item_that_matches_the_predicate() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        for item in 0..10 {
+            subject.enqueue(item, item % 4);
+        }
+
+        // Current state of bucket queue:
+        //   0: 0, 4, 8
+        //   1: 1, 5, 9
+        //   2: 2, 6
+        //   3: 3, 7
+
+        assert_eq!(subject.remove_first_where(|_, &item| item > 7), Some((0, 8)));
+        assert_eq!(subject.remove_first_where(|p, _| p == 3), Some((3, 3)));
+        assert_eq!(subject.remove_first_where(|_, &item| item > 10), None);
+
+        assert_eq!(subject.len(), 8);
+        assert_eq!(subject.dequeue(0), Some(0));
+        assert_eq!(subject.dequeue(0), Some(4));
+        assert_eq!(subject.dequeue(0), None);
+        assert_eq!(subject.dequeue(3), Some(7));
+        assert_eq!(subject.max_priority(), Some(2));
+    }
+
+    #[test]
+    fn it_updates_the_min_when_the_last_item_in_a_bucket_is_removed() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 3);
+        subject.push("second", 5);
+
+        assert_eq!(subject.remove_first_where(|_, &item| item == "first"), Some((3, "first")));
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(5));
+    }
+
+    #[test]
+    fn it_can_retain_items_in_deferred_buckets() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        for item in 0..10 {
+            subject.enqueue(item, item % 4);
+        }
+
+        // Current state of bucket queue:
+        //   0: 0, 4, 8
+        //   1: 1, 5, 9
+        //   2: 2, 6
+        //   3: 3, 7
+
+        subject.bucket(1).retain_bucket(|&item| item != 5);
+        assert_eq!(subject.bucket(2).remove_first_from_bucket(|&item| item == 6), Some(6));
+
+        subject.bucket(7).retain_bucket(|_| false);
+        assert_eq!(subject.bucket(7).remove_first_from_bucket(|_| true), None);
+
+        assert_eq!(subject.len(), 8);
+        assert_eq!(subject.max_priority(), Some(3));
+        assert_eq!(subject.dequeue(1), Some(1));
+        assert_eq!(subject.dequeue(1), Some(9));
+        assert_eq!(subject.dequeue(2), Some(2));
+        assert_eq!(subject.dequeue(2), None);
+    }
+
+    #[test]
+    fn it_can_retain_items_in_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 4);
+        subject.bucket(1).push("second", 6);
+        subject.bucket(2).push("third", 5);
+
+        subject.retain(|_, &item| item != "second");
+        assert_eq!(subject.bucket(1).max_priority(), Some(4));
+
+        let removed = subject.bucket(2).remove_first_from_bucket(|_| true);
+        assert_eq!(removed, Some("third"));
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.max_priority(), Some(1));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+    }
+}
+
+mod extend {
+    use super::*;
+
+    #[test]
+    fn it_can_be_collected_from_items_and_priorities() {
+        let items = vec![("first", 3), ("second", 1), ("third", 3), ("fourth", 7)];
+        let mut subject: Subject<VecDeque<&'static str>> = items.into_iter().collect();
+
+        assert_eq!(subject.len(), 4);
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(7));
+
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+        assert_eq!(subject.dequeue_min(), None);
+    }
+
+    #[test]
+    fn it_adds_items_in_last_in_first_out_order() {
+        let items = vec![("first", 2), ("second", 2), ("third", 0)];
+        let mut subject: Subject<Vec<&'static str>> = items.into_iter().collect();
+
+        assert_eq!(subject.pop_max(), Some("second"));
+        assert_eq!(subject.pop_max(), Some("first"));
+        assert_eq!(subject.pop_max(), Some("third"));
+    }
+
+    #[test]
+    fn it_adds_items_to_the_back_of_double_ended_buckets() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.push_front("first", 2);
+        subject.extend(vec![("second", 2), ("third", 2)]);
+
+        assert_eq!(subject.pop_back_min(), Some("third"));
+        assert_eq!(subject.pop_front_min(), Some("first"));
+        assert_eq!(subject.pop_front_min(), Some("second"));
+    }
+
+    #[test]
+    fn it_extends_a_queue_that_already_has_items() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        subject.enqueue(1, 1_000);
+        subject.enqueue(2, 1_010);
+
+        subject.extend(vec![(3, 5), (4, 2_000), (5, 1_000)]);
+        subject.extend(Vec::new());
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(2_000));
+
+        let drained: Vec<_> = subject.drain_min().collect();
+        assert_eq!(drained, vec![(5, 3), (1_000, 1), (1_000, 5), (1_010, 2), (2_000, 4)]);
+    }
+
+    #[test]
+    fn it_is_equivalent_to_enqueuing_each_item() {
+        let items: Vec<_> = (0..100).map(|i| (i, (i * 37) % 23)).collect();
+
+        let mut expected = Subject::<VecDeque<usize>>::new();
+
+        for &(item, priority) in &items {
+            expected.enqueue(item, priority);
+        }
+
+        let mut actual: Subject<VecDeque<usize>> = items.into_iter().collect();
+        assert_eq!(actual.len(), expected.len());
+
+        let actual: Vec<_> = actual.drain_max().collect();
+        let expected: Vec<_> = expected.drain_max().collect();
+
+        assert_eq!(actual, expected);
+    }
+}
+
+mod append {
+    use super::*;
+
+    #[test]
+    fn it_moves_all_items_from_the_other_queue() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+        let mut other = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        other.enqueue("third", 3);
+        other.enqueue("fourth", 1);
+        other.enqueue("fifth", 8);
+
+        subject.append(&mut other);
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(8));
+
+        assert_eq!(other.len(), 0);
+        assert_eq!(other.is_empty(), true);
+        assert_eq!(other.min_priority(), None);
+
+        assert_eq!(subject.dequeue_min(), Some("fourth"));
+        assert_eq!(subject.dequeue_min(), Some("first"));
+        assert_eq!(subject.dequeue_min(), Some("third"));
+        assert_eq!(subject.dequeue_min(), Some("second"));
+        assert_eq!(subject.dequeue_min(), Some("fifth"));
+    }
+
+    #[test]
+    fn it_pops_appended_items_first_for_last_in_first_out_buckets() {
+        let mut subject = Subject::<Vec<&'static str>>::new();
+        let mut other = Subject::<Vec<&'static str>>::new();
+
+        subject.push("first", 2);
+        other.push("second", 2);
+
+        subject.append(&mut other);
+
+        assert_eq!(subject.pop_min(), Some("second"));
+        assert_eq!(subject.pop_min(), Some("first"));
+    }
+
+    #[test]
+    fn it_supports_queues_with_different_ranges_of_priorities() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        let mut other = Subject::<Vec<usize>>::new();
+
+        subject.push(1, 1_000);
+        other.push(2, 10);
+        other.push(3, 5_000);
+
+        subject.append(&mut other);
+        other.push(4, 7);
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(other.len(), 1);
+
+        let drained: Vec<_> = subject.drain_min().collect();
+        assert_eq!(drained, vec![(10, 2), (1_000, 1), (5_000, 3)]);
+    }
+
+    #[test]
+    fn it_can_append_to_and_from_empty_queues() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        let mut other = Subject::<Vec<usize>>::new();
+
+        subject.append(&mut other);
+        assert_eq!(subject.is_empty(), true);
+
+        other.push(1, 4);
+        subject.append(&mut other);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(other.len(), 0);
+
+        subject.append(&mut other);
+        assert_eq!(subject.pop_min(), Some(1));
+    }
+
+    #[test]
+    fn it_can_append_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+        let mut other = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 4);
+        other.bucket(1).push("second", 2);
+        other.bucket(0).push("third", 9);
+
+        subject.append(&mut other);
+
+        assert_eq!(subject.len(), 3);
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("second"));
+        assert_eq!(subject.min_bucket().pop_min(), Some("first"));
+    }
+}
+
+mod split {
+    use super::*;
+
+    #[test]
+    fn it_splits_off_the_buckets_at_or_above_the_priority() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        for item in 0..10 {
+            subject.push(item, 100 + item * 10);
+        }
+
+        // Current state of bucket queue:
+        //   100: 0
+        //   110: 1
+        //   ...
+        //   190: 9
+
+        let mut above = subject.split_off(150);
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priority(), Some(100));
+        assert_eq!(subject.max_priority(), Some(140));
+
+        assert_eq!(above.len(), 5);
+        assert_eq!(above.min_priority(), Some(150));
+        assert_eq!(above.max_priority(), Some(190));
+
+        assert_eq!(above.pop_min(), Some(5));
+        assert_eq!(subject.pop_max(), Some(4));
+    }
+
+    #[test]
+    fn it_splits_off_the_buckets_below_the_priority() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        for item in 0..10 {
+            subject.push(item, 100 + item * 10);
+        }
+
+        // Current state of bucket queue:
+        //   100: 0
+        //   110: 1
+        //   ...
+        //   190: 9
+
+        let mut below = subject.split_below(155);
+
+        assert_eq!(below.len(), 6);
+        assert_eq!(below.min_priority(), Some(100));
+        assert_eq!(below.max_priority(), Some(150));
+
+        assert_eq!(subject.len(), 4);
+        assert_eq!(subject.min_priority(), Some(160));
+        assert_eq!(subject.max_priority(), Some(190));
+
+        assert_eq!(below.pop_max(), Some(5));
+        assert_eq!(subject.pop_min(), Some(6));
+    }
+
+    #[test]
+    fn it_moves_everything_or_nothing_at_the_extremes() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        for item in 0..10 {
+            subject.push(item, 100 + item * 10);
+        }
+
+        // Current state of bucket queue:
+        //   100: 0
+        //   110: 1
+        //   ...
+        //   190: 9
+
+        assert_eq!(subject.split_off(500).is_empty(), true);
+        assert_eq!(subject.split_below(50).is_empty(), true);
+        assert_eq!(subject.len(), 10);
+
+        let mut all = subject.split_off(100);
+
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(all.len(), 10);
+
+        let all = all.split_below(1_000);
+        assert_eq!(all.len(), 10);
+        assert_eq!(all.max_priority(), Some(190));
+    }
+
+    #[test]
+    fn it_keeps_working_after_the_split() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        for item in 0..10 {
+            subject.push(item, 100 + item * 10);
+        }
+
+        // Current state of bucket queue:
+        //   100: 0
+        //   110: 1
+        //   ...
+        //   190: 9
+
+        let mut above = subject.split_off(150);
+
+        subject.push(10, 300);
+        subject.push(11, 50);
+        above.push(12, 0);
+
+        assert_eq!(subject.len(), 7);
+        assert_eq!(subject.min_priority(), Some(50));
+        assert_eq!(subject.max_priority(), Some(300));
+
+        assert_eq!(above.len(), 6);
+        assert_eq!(above.pop_min(), Some(12));
+        assert_eq!(above.pop_min(), Some(5));
+    }
+
+    #[test]
+    fn it_can_split_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 4);
+        subject.bucket(3).push("second", 2);
+        subject.bucket(3).push("third", 9);
+
+        let mut above = subject.split_off(2);
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(above.len(), 2);
+        assert_eq!(above.max_bucket().pop_max(), Some("third"));
+    }
+}
+
+mod take_bucket {
+    use super::*;
+
+    #[test]
+    fn it_takes_the_bucket_with_minimum_or_maximum_priority() {
+        let mut subject = Subject::<VecDeque<&'static str>>::new();
+
+        subject.enqueue("first", 3);
+        subject.enqueue("second", 5);
+        subject.enqueue("third", 3);
+        subject.enqueue("fourth", 7);
+
+        let (priority, bucket) = subject.take_min_bucket().unwrap();
+
+        assert_eq!(priority, 3);
+        assert_eq!(bucket, &["first", "third"]);
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(5));
+
+        let (priority, bucket) = subject.take_max_bucket().unwrap();
+
+        assert_eq!(priority, 7);
+        assert_eq!(bucket, &["fourth"]);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.max_priority(), Some(5));
+
+        assert_eq!(subject.take_max_bucket().map(|(p, _)| p), Some(5));
+        assert_eq!(subject.take_min_bucket(), None);
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_can_recycle_an_empty_bucket_in_place_of_the_one_taken() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        let mut batches = vec![];
+        let mut recycled = Vec::with_capacity(10);
+
+        subject.push(1, 0);
+        subject.push(2, 0);
+        subject.push(3, 1);
+
+        while let Some((priority, mut batch)) = subject.swap_min_bucket(recycled) {
+            batches.push((priority, batch.clone()));
+
+            batch.clear();
+            recycled = batch;
+        }
+
+        assert_eq!(batches, vec![(0, vec![1, 2]), (1, vec![3])]);
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_can_take_buckets_from_nested_bucket_queues() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 4);
+        subject.bucket(1).push("second", 6);
+        subject.bucket(2).push("third", 5);
+
+        let (priority, bucket) = subject.min_bucket().take_max_bucket().unwrap();
+
+        assert_eq!(priority, 6);
+        assert_eq!(bucket, vec!["second"]);
+        assert_eq!(subject.len(), 2);
+
+        let (priority, mut bucket) = subject.take_max_bucket().unwrap();
+
+        assert_eq!(priority, 2);
+        assert_eq!(bucket.pop_min(), Some("third"));
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.max_priority(), Some(1));
+    }
+}
+
+mod capacity {
+    use super::*;
+
+    #[test]
+    fn it_can_be_created_with_preallocated_buckets() {
+        let mut subject = Subject::<Vec<usize>>::with_capacity(9, 16);
+
+        assert_eq!(subject.len_buckets(), 10);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.bucket_for_peeking(9).unwrap().capacity() >= 16, true);
+
+        subject.push(1, 9);
+        subject.push(2, 0);
+
+        assert_eq!(subject.len_buckets(), 10);
+        assert_eq!(subject.min_priority(), Some(0));
+        assert_eq!(subject.pop_max(), Some(1));
+    }
+
+    #[test]
+    fn it_can_reserve_a_bucket_for_a_priority() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        subject.reserve_priority(100);
+        subject.reserve_priority(95);
+
+        assert_eq!(subject.len_buckets(), 6);
+        assert_eq!(subject.is_empty(), true);
+        assert_eq!(subject.bucket_for_peeking(95).is_some(), true);
+        assert_eq!(subject.bucket_for_peeking(96).is_some(), false);
+
+        subject.push(1, 100);
+        assert_eq!(subject.min_priority(), Some(100));
+    }
+
+    #[test]
+    fn it_shrinks_to_fit_the_range_of_non_empty_buckets() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        for priority in 0..20 {
+            subject.push(priority, priority);
+        }
+
+        subject.retain(|priority, _| (5..=10).contains(&priority) && priority != 7);
+        subject.shrink_to_fit();
+
+        assert_eq!(subject.len_buckets(), 6);
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.bucket_for_peeking(7).is_some(), false);
+        assert_eq!(subject.min_priority(), Some(5));
+        assert_eq!(subject.max_priority(), Some(10));
+
+        subject.push(20, 0);
+        assert_eq!(subject.pop_min(), Some(20));
+        assert_eq!(subject.pop_max(), Some(10));
+
+        subject.clear();
+        subject.shrink_to_fit();
+
+        assert_eq!(subject.len_buckets(), 0);
+    }
+
+    #[test]
+    fn it_does_not_pool_buckets_by_default() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        subject.push(1, 0);
+        subject.clear();
+
+        assert_eq!(subject.len_pool(), 0);
+    }
+
+    #[test]
+    fn it_reuses_pooled_buckets_when_priorities_move_upwards() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        subject.set_max_pooled(2);
+
+        for priority in 0..4 {
+            for item in 0..100 {
+                subject.push(item, priority);
+            }
+        }
+
+        while subject.min_priority() != Some(3) {
+            subject.pop_min();
+        }
+
+        subject.push(10, 10);
+
+        assert_eq!(subject.len_pool(), 1);
+        assert_eq!(subject.bucket_for_peeking(10).unwrap().capacity() >= 100, true);
+
+        assert_eq!(subject.len(), 101);
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(10));
+    }
+
+    #[test]
+    fn it_pools_buckets_when_cleared() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        subject.set_max_pooled(3);
+
+        for priority in 0..5 {
+            subject.push(priority, priority);
+        }
+
+        subject.clear();
+
+        assert_eq!(subject.len_pool(), 3);
+        assert_eq!(subject.is_empty(), true);
+
+        subject.push(1, 50);
+        assert_eq!(subject.len_pool(), 2);
+
+        subject.set_max_pooled(1);
+        assert_eq!(subject.len_pool(), 1);
+
+        subject.shrink_to_fit();
+        assert_eq!(subject.len_pool(), 0);
+        assert_eq!(subject.pop_min(), Some(1));
+    }
+
+    #[test]
+    fn it_keeps_the_pool_when_splitting_off_every_bucket() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        subject.set_max_pooled(3);
+
+        for priority in 0..5 {
+            subject.push(priority, priority);
+        }
+
+        let other = subject.split_off(0);
+
+        assert_eq!(other.len(), 5);
+        assert_eq!(subject.is_empty(), true);
+
+        for priority in 0..5 {
+            subject.push(priority, priority);
+        }
+
+        subject.clear();
+        assert_eq!(subject.len_pool(), 3);
+    }
+
+    #[test]
+    fn it_keeps_the_pool_when_splitting_below_every_bucket() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        subject.set_max_pooled(3);
+
+        for priority in 0..5 {
+            subject.push(priority, priority);
+        }
+
+        let other = subject.split_below(10);
+
+        assert_eq!(other.len(), 5);
+        assert_eq!(subject.is_empty(), true);
+
+        for priority in 0..5 {
+            subject.push(priority, priority);
+        }
+
+        subject.clear();
+        assert_eq!(subject.len_pool(), 3);
+    }
+
+    #[test]
+    fn it_keeps_the_pool_of_each_queue_when_appending_into_an_empty_queue() {
+        let mut subject = Subject::<Vec<usize>>::new();
+        subject.set_max_pooled(2);
+
+        let mut other = Subject::<Vec<usize>>::new();
+        other.set_max_pooled(3);
+
+        for priority in 0..5 {
+            other.push(priority, priority);
+        }
+
+        subject.append(&mut other);
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(other.is_empty(), true);
+
+        subject.clear();
+        assert_eq!(subject.len_pool(), 2);
+
+        for priority in 0..5 {
+            other.push(priority, priority);
+        }
+
+        other.clear();
+        assert_eq!(other.len_pool(), 3);
+    }
+}
+
+mod with_bucket {
+    use super::*;
+
+    #[test]
+    fn it_can_add_many_items_to_a_nested_queue() {
+        let mut subject = Subject::<Subject<Vec<usize>>>::new();
+
+        let len = subject.with_bucket(3, |bucket| {
+            for item in 0..10 {
+                bucket.push(item, item % 3);
+            }
+
+            bucket.len()
+        });
+
+        assert_eq!(len, 10);
+        assert_eq!(subject.len(), 10);
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(3));
+        assert_eq!(subject.bucket(3).max_priority(), Some(2));
+    }
+
+    #[test]
+    fn it_can_add_and_remove_items_from_the_same_bucket() {
+        let mut subject = Subject::<Vec<usize>>::new();
+
+        subject.push(1, 2);
+        subject.push(2, 5);
+
+        subject.with_bucket(5, |bucket| {
+            bucket.pop();
+            bucket.push(3);
+            bucket.push(4);
+            bucket.pop();
+        });
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.pop_max(), Some(3));
+
+        subject.with_bucket(5, |bucket| bucket.clear());
+        subject.with_bucket(2, |bucket| bucket.clear());
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.min_priority(), None);
+        assert_eq!(subject.max_priority(), None);
+    }
+
+    #[test]
+    fn it_updates_the_min_and_max_priority_when_a_bucket_is_emptied() {
+        let mut subject = Subject::<Subject<Vec<usize>>>::new();
+
+        subject.bucket(1).push(1, 0);
+        subject.bucket(2).push(2, 0);
+        subject.bucket(2).push(3, 1);
+        subject.bucket(3).push(4, 0);
+
+        let popped = subject.with_bucket(2, |bucket| {
+            bucket.drain_max().map(|(_, item)| item).collect::<Vec<_>>()
+        });
+
+        assert_eq!(popped, vec![3, 2]);
+        assert_eq!(subject.len(), 2);
+
+        subject.with_bucket(3, |bucket| bucket.pop_min());
+
+        assert_eq!(subject.min_priority(), Some(1));
+        assert_eq!(subject.max_priority(), Some(1));
+    }
+
+    #[test]
+    fn it_can_be_used_on_deferred_buckets() {
+        let mut subject = Subject::<Subject<Subject<Vec<usize>>>>::new();
+
+        subject.bucket(1).with_bucket(2, |bucket| {
+            bucket.push(1, 3);
+            bucket.push(2, 4);
+        });
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.bucket(1).len_queue(), 2);
+        assert_eq!(subject.bucket(1).bucket(2).pop_max(), Some(2));
+        assert_eq!(subject.len(), 1);
+    }
+
+    #[test]
+    fn it_can_remove_items_from_deferred_buckets() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.bucket(1).push("first", 2);
+        subject.bucket(1).push("second", 2);
+        subject.bucket(3).push("third", 0);
+
+        let popped = subject.bucket(1).with_bucket(2, |bucket| bucket.pop());
+
+        assert_eq!(popped, Some("second"));
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.min_priority(), Some(1));
+
+        subject.bucket(1).with_bucket(2, |bucket| bucket.clear());
+
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.min_priority(), Some(3));
+        assert_eq!(subject.max_priority(), Some(3));
+        assert_eq!(subject.min_bucket().pop_min(), Some("third"));
+        assert_eq!(subject.is_empty(), true);
+    }
+}
+
+mod lexicographic {
+    use super::*;
+
+    #[test]
+    fn it_can_enqueue_and_dequeue_with_tuples_of_priorities() {
+        let mut subject = Subject::<Subject<Subject<VecDeque<&'static str>>>>::new();
+
+        subject.enqueue_at("first", (1, 0, 2));
+        subject.enqueue_at("second", (0, 5, 5));
+        subject.enqueue_at("third", (1, 0, 1));
+        subject.enqueue_at("fourth", (1, 0, 1));
+        subject.enqueue_at("fifth", (0, 5, 6));
+
+        assert_eq!(subject.len(), 5);
+        assert_eq!(subject.min_priorities(), Some((0, 5, 5)));
+        assert_eq!(subject.max_priorities(), Some((1, 0, 2)));
+
+        assert_eq!(subject.dequeue_min_at(), Some(((0, 5, 5), "second")));
+        assert_eq!(subject.dequeue_max_at(), Some(((1, 0, 2), "first")));
+        assert_eq!(subject.dequeue_max_at(), Some(((1, 0, 1), "third")));
+        assert_eq!(subject.dequeue_min_at(), Some(((0, 5, 6), "fifth")));
+        assert_eq!(subject.dequeue_min_at(), Some(((1, 0, 1), "fourth")));
+        assert_eq!(subject.dequeue_min_at(), None::<((usize, usize, usize), _)>);
+
+        assert_eq!(subject.len(), 0);
+        assert_eq!(subject.min_priority(), None);
+    }
+
+    #[test]
+    fn it_can_push_and_pop_with_tuples_of_priorities() {
+        let mut subject = Subject::<Subject<Vec<&'static str>>>::new();
+
+        subject.push_at("first", (3, 1));
+        subject.push_at("second", (3, 1));
+        subject.push_at("third", (2, 7));
+
+        assert_eq!(subject.pop_min_at(), Some(((2, 7), "third")));
+        assert_eq!(subject.pop_min_at(), Some(((3, 1), "second")));
+        assert_eq!(subject.pop_max_at(), Some(((3, 1), "first")));
+        assert_eq!(subject.pop_max_at(), None::<((usize, usize), _)>);
+    }
+
+    #[test]
+    fn it_infers_the_depth_from_the_pattern_of_priorities() {
+        let mut subject = Subject::<Subject<VecDeque<usize>>>::new();
+        let mut popped = vec![];
+
+        subject.enqueue_at(1, (2, 3));
+        subject.enqueue_at(2, (2, 1));
+
+        while let Some(((a, b), item)) = subject.dequeue_min_at() {
+            popped.push((a, b, item));
+        }
+
+        assert_eq!(popped, vec![(2, 1, 2), (2, 3, 1)]);
+    }
+
+    #[test]
+    fn it_is_equivalent_to_nested_deferred_buckets() {
+        let mut subject = Subject::<Subject<Subject<VecDeque<usize>>>>::new();
+
+        subject.bucket(4).bucket(2).enqueue(1, 3);
+        subject.enqueue_at(2, (4, 2, 3));
+
+        assert_eq!(subject.len(), 2);
+        assert_eq!(subject.bucket(4).len_queue(), 2);
+        assert_eq!(subject.bucket(4).bucket(2).len_queue(), 2);
+
+        assert_eq!(subject.dequeue_min_at(), Some(((4, 2, 3), 1)));
+        assert_eq!(subject.bucket(4).bucket(2).dequeue_min(), Some(2));
+        assert_eq!(subject.is_empty(), true);
+    }
+
+    #[test]
+    fn it_does_not_remove_anything_if_the_leaf_bucket_is_empty() {
+        let mut subject = Subject::<Subject<VecDeque<usize>>>::new();
+
+        subject.enqueue_at(1, (0, 0));
+
+        assert_eq!(subject.leaf_for_removing((0, 1)).is_none(), true);
+        assert_eq!(subject.leaf_for_removing((1, 0)).is_none(), true);
+        assert_eq!(subject.len(), 1);
+        assert_eq!(subject.leaf_for_peeking((0, 0)).map(|b| b.len()), Some(1));
+    }
+
+    #[test]
+    fn it_supports_different_types_of_priority_at_each_level() {
+        let mut subject = BucketQueue::<BucketQueue<VecDeque<char>, SimpleIndex, i8>, SimpleIndex, u8>::new();
+
+        subject.enqueue_at('a', (1, -5));
+        subject.enqueue_at('b', (1, -10));
+        subject.enqueue_at('c', (0, 100));
+
+        assert_eq!(subject.dequeue_min_at(), Some(((0, 100), 'c')));
+        assert_eq!(subject.dequeue_min_at(), Some(((1, -10), 'b')));
+        assert_eq!(subject.dequeue_min_at(), Some(((1, -5), 'a')));
+    }
+}
+
+mod order {
+    use super::*;
+
+    #[test]
+    fn it_can_minimise_one_level_and_maximise_another() {
+        let mut subject = Subject::<Subject<VecDeque<&'static str>>>::new();
+
+        subject.enqueue_at("first", (1, 2));
+        subject.enqueue_at("second", (1, 8));
+        subject.enqueue_at("third", (0, 3));
+        subject.enqueue_at("fourth", (0, 5));
+
+        assert_eq!(subject.next_priorities::<(Min, Max)>(), Some((0, 5)));
+        assert_eq!(subject.next_priorities::<(Max, Min)>(), Some((1, 2)));
+
+        assert_eq!(subject.dequeue_next::<(Min, Max)>(), Some(((0, 5), "fourth")));
+        assert_eq!(subject.dequeue_next::<(Min, Max)>(), Some(((0, 3), "third")));
+        assert_eq!(subject.dequeue_next::<(Min, Max)>(), Some(((1, 8), "second")));
+        assert_eq!(subject.dequeue_next::<(Min, Max)>(), Some(((1, 2), "first")));
+        assert_eq!(subject.dequeue_next::<(Min, Max)>(), None::<((usize, usize), _)>);
+    }
+
+    #[test]
+    fn it_applies_a_single_order_to_all_remaining_levels() {
+        let mut subject = Subject::<Subject<Subject<Vec<usize>>>>::new();
+
+        subject.push_at(1, (0, 0, 0));
+        subject.push_at(2, (0, 1, 1));
+        subject.push_at(3, (0, 1, 2));
+        subject.push_at(4, (1, 0, 0));
+
+        assert_eq!(subject.next_priorities::<Min>(), Some((0, 0, 0)));
+        assert_eq!(subject.next_priorities::<Max>(), Some((1, 0, 0)));
+
+        assert_eq!(subject.pop_next::<(Min, Max)>(), Some(((0, 1, 2), 3)));
+        assert_eq!(subject.pop_next::<(Min, Max, Min)>(), Some(((0, 1, 1), 2)));
+        assert_eq!(subject.pop_next::<(Max, Min)>(), Some(((1, 0, 0), 4)));
+        assert_eq!(subject.len(), 1);
+    }
+
+    #[test]
+    fn it_can_be_declared_once_with_a_type_alias() {
+        type Deadline = (Min, Max);
+
+        let mut subject = Subject::<Subject<VecDeque<usize>>>::new();
+
+        subject.enqueue_at(1, (3, 1));
+        subject.enqueue_at(2, (3, 9));
+
+        assert_eq!(subject.dequeue_next::<Deadline>(), Some(((3, 9), 2)));
+        assert_eq!(subject.dequeue_next::<Deadline>(), Some(((3, 1), 1)));
+    }
+
+    #[test]
+    fn it_can_be_used_with_a_single_level() {
+        let mut subject = Subject::<VecDeque<usize>>::new();
+
+        subject.enqueue(1, 1);
+        subject.enqueue(2, 2);
+
+        assert_eq!(subject.dequeue_next::<Max>(), Some((2, 2)));
+        assert_eq!(subject.dequeue_next::<Max>(), Some((1, 1)));
+    }
+}